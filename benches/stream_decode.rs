@@ -0,0 +1,95 @@
+//! Decoding frames of the real-time stock data stream, in JSON and in MessagePack.
+use alpaca_rs::{api::market_data::live::Encoding, model::StockStreamMessage};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use serde::Serialize;
+use serde_with::{serde_as, Bytes, Same};
+
+const TRADES: usize = 1000;
+
+/// A MessagePack timestamp, which rmp-serde serializes as an extension type.
+#[serde_as]
+#[derive(Serialize, Clone)]
+#[serde(rename = "_ExtStruct")]
+struct MsgPackTimestamp(#[serde_as(as = "(Same, Bytes)")] (i8, Vec<u8>));
+
+impl MsgPackTimestamp {
+    fn new(seconds: i64, nanos: u32) -> Self {
+        let mut data = nanos.to_be_bytes().to_vec();
+        data.extend(seconds.to_be_bytes());
+        Self((-1, data))
+    }
+}
+
+#[derive(Serialize)]
+struct Trade<T> {
+    #[serde(rename = "T")]
+    kind: &'static str,
+    #[serde(rename = "S")]
+    symbol: &'static str,
+    #[serde(rename = "t")]
+    timestamp: T,
+    #[serde(rename = "x")]
+    exchange: &'static str,
+    #[serde(rename = "p")]
+    price: f64,
+    #[serde(rename = "s")]
+    size: u32,
+    #[serde(rename = "i")]
+    id: u64,
+    #[serde(rename = "c")]
+    conditions: [&'static str; 1],
+    #[serde(rename = "z")]
+    tape: &'static str,
+}
+
+fn trades<T: Clone>(timestamp: T) -> Vec<Trade<T>> {
+    (0..TRADES as u64)
+        .map(|id| Trade {
+            kind: "t",
+            symbol: "AAPL",
+            timestamp: timestamp.clone(),
+            exchange: "V",
+            price: 189.25,
+            size: 100,
+            id,
+            conditions: ["@"],
+            tape: "C",
+        })
+        .collect()
+}
+
+fn decode(c: &mut Criterion) {
+    let json = serde_json::to_vec(&trades("2024-01-02T15:04:05.123456789Z")).unwrap();
+    let msgpack =
+        rmp_serde::to_vec_named(&trades(MsgPackTimestamp::new(1_704_207_845, 123_456_789)))
+            .unwrap();
+
+    // both frames have to decode into the same messages for the comparison to be fair
+    assert_eq!(
+        Encoding::Json.decode::<StockStreamMessage>(&json).unwrap(),
+        Encoding::MessagePack
+            .decode::<StockStreamMessage>(&msgpack)
+            .unwrap()
+    );
+
+    let mut group = c.benchmark_group("decode trades");
+    group.throughput(Throughput::Elements(TRADES as u64));
+    group.bench_function("json", |b| {
+        b.iter(|| {
+            Encoding::Json
+                .decode::<StockStreamMessage>(black_box(&json))
+                .unwrap()
+        })
+    });
+    group.bench_function("msgpack", |b| {
+        b.iter(|| {
+            Encoding::MessagePack
+                .decode::<StockStreamMessage>(black_box(&msgpack))
+                .unwrap()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, decode);
+criterion_main!(benches);
@@ -29,7 +29,7 @@ struct Service {
 }
 
 impl Service {
-    async fn limit_order(&self, limit_price: f64, side: OrderSide, qty: i64) -> Result<Order> {
+    async fn limit_order(&self, limit_price: f64, side: OrderSide, qty: f64) -> Result<Order> {
         info!(
             "Creating a {side} limit order (at ${limit_price}) for {qty} shares of {}",
             self.stock
@@ -45,6 +45,9 @@ impl Service {
                 side,
                 time_in_force: OrderTif::Day,
                 client_order_id: None,
+                take_profit: None,
+                stop_loss: None,
+                legs: None,
             })
             .await
     }
@@ -123,6 +126,9 @@ impl Service {
                             time_in_force: OrderTif::Day,
                             extended_hours: false,
                             client_order_id: None,
+                            take_profit: None,
+                            stop_loss: None,
+                            legs: None,
                         })
                         .await?;
 
@@ -175,7 +181,7 @@ impl Service {
         if current_price > self.running_avg {
             info!("price was above running average, liquidating positions");
             // liquidate our position if the price is above the running averange
-            if pos_qty > 0 {
+            if pos_qty > 0.0 {
                 info!("liquidating {pos_qty} positions at ${current_price} per share");
 
                 self.last_order = Some(
@@ -204,14 +210,14 @@ impl Service {
                 if amount_to_add > buying_power {
                     amount_to_add = buying_power;
                 }
-                let qty_to_buy = (amount_to_add / current_price).floor() as i64;
+                let qty_to_buy = (amount_to_add / current_price).floor();
                 self.last_order = Some(
                     self.limit_order(current_price, OrderSide::Buy, qty_to_buy)
                         .await?,
                 );
             } else {
                 amount_to_add *= -1.0;
-                let qty_to_sell = ((amount_to_add / current_price).floor() as i64).max(pos_qty);
+                let qty_to_sell = (amount_to_add / current_price).floor().max(pos_qty);
                 self.last_order = Some(
                     self.limit_order(current_price, OrderSide::Sell, qty_to_sell)
                         .await?,
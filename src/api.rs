@@ -6,9 +6,13 @@
 //! - (WIP) the [Market Data API](https://docs.alpaca.markets/docs/about-market-data-api) - [`mod@market_data`]
 use super::*;
 use crate::model::*;
+use rate_limit::{RateLimit, RateLimiter};
+use retry::RetryPolicy;
 
 pub mod broker;
 pub mod market_data;
+pub mod rate_limit;
+pub mod retry;
 pub mod trading;
 
 // hack: chrono is reexported from serde_with (we use a glob import) so we override that with an extern crate
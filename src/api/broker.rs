@@ -12,11 +12,17 @@ pub use trading::*;
 /// The credentials used to authenticate with the Alpaca [Broker API](https://docs.alpaca.markets/docs/about-broker-api).
 ///
 /// The key is a [`String`], that means you don't need to encode it as Base64 because this library does that automatically.
+#[derive(Clone)]
 pub struct BrokerAuth {
     pub key: String,
 }
 
-struct BrokerMiddleware(BrokerAuth);
+#[derive(Clone)]
+struct BrokerMiddleware {
+    auth: BrokerAuth,
+    rate_limiter: RateLimiter,
+    retry: RetryPolicy,
+}
 
 impl Service for BrokerMiddleware {
     type Error = Error;
@@ -29,11 +35,11 @@ impl Middleware for BrokerMiddleware {
             AUTHORIZATION,
             format!(
                 "Basic {}",
-                base64::engine::general_purpose::STANDARD.encode(&self.0.key)
+                base64::engine::general_purpose::STANDARD.encode(&self.auth.key)
             ),
         );
 
-        Ok(NoMiddleware.call(request).await?)
+        Error::for_status(self.rate_limiter.send(request, &self.retry).await?).await
     }
 }
 
@@ -70,7 +76,40 @@ impl BrokerClient {
     }
 
     pub fn new(auth: BrokerAuth, base_url: Url) -> Self {
-        Self(HttpClient::new_with(BrokerMiddleware(auth)).with_base_url(base_url))
+        Self(
+            HttpClient::new_with(BrokerMiddleware {
+                auth,
+                rate_limiter: RateLimiter::new(RateLimit::default()),
+                retry: RetryPolicy::default(),
+            })
+            .with_base_url(base_url),
+        )
+    }
+
+    fn map_middleware(self, f: impl FnOnce(BrokerMiddleware) -> BrokerMiddleware) -> Self {
+        Self(
+            HttpClient::new_with(f(self.0.get_middleware().clone()))
+                .with_base_url(self.0.base_url().cloned().unwrap()),
+        )
+    }
+
+    /// Configures how this client handles the rate limit, see [`RateLimit`].
+    ///
+    /// The [`AccountView`]s created from this client share its rate limit.
+    pub fn with_rate_limit(self, rate_limit: RateLimit) -> Self {
+        self.map_middleware(|middleware| BrokerMiddleware {
+            rate_limiter: RateLimiter::new(rate_limit),
+            ..middleware
+        })
+    }
+
+    /// Configures how this client (and the [`AccountView`]s created from it) retries failed
+    /// requests, see [`RetryPolicy`].
+    pub fn with_retry_policy(self, retry: RetryPolicy) -> Self {
+        self.map_middleware(|middleware| BrokerMiddleware {
+            retry,
+            ..middleware
+        })
     }
 
     pub async fn execute<T: ClientEndpoint<Context = Self, Error = Error>>(
@@ -83,9 +122,7 @@ impl BrokerClient {
     pub async fn account(&self, id: &str) -> AccountView {
         AccountView::new(
             id.to_owned(),
-            BrokerMiddleware(BrokerAuth {
-                key: self.0.get_middleware().0.key.to_owned(),
-            }),
+            self.0.get_middleware().clone(),
             self.0.base_url().cloned().unwrap(),
         )
     }
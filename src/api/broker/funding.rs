@@ -50,7 +50,7 @@ pub struct CreateTransfer {
     pub bank_id: Option<String>,
     #[required]
     #[serde_as(as = "DisplayFromStr")]
-    pub amount: f64,
+    pub amount: Num,
     #[required]
     pub direction: Direction,
     #[required]
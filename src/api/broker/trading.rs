@@ -1,14 +1,19 @@
 use super::*;
 
 /// Create an order on behalf of an account in the Broker API.
+///
+/// Like a [`CreateOrder`](crate::api::trading::CreateOrder), the order is
+/// [validated](CreateOrderBroker::validate) when it is serialized.
 #[with_builder(create_order)]
 #[skip_serializing_none]
 #[serde_as]
 #[derive(Serialize, Deserialize, Debug, Clone, ClientEndpoint)]
+#[serde(remote = "Self")]
 #[endpoint(Post(json) "/orders" in AccountView -> Order)]
 pub struct CreateOrderBroker {
-    /// The symbol/ticker of the stock being traded.
+    /// The symbol/ticker of the stock being traded, empty for multi-leg orders.
     #[required]
+    #[serde(skip_serializing_if = "String::is_empty")]
     pub symbol: String,
     /// Either the quantity or the dollar amount to trade.
     #[required]
@@ -28,12 +33,18 @@ pub struct CreateOrderBroker {
     pub client_order_id: Option<String>,
     // TODO explain
     pub order_class: OrderClass,
+    /// The take-profit leg of a bracket, OCO or OTO order.
+    pub take_profit: Option<TakeProfit>,
+    /// The stop-loss leg of a bracket, OCO or OTO order.
+    pub stop_loss: Option<StopLoss>,
+    /// The legs of a multi-leg options order.
+    pub legs: Option<Vec<OrderLeg>>,
     /// The commission that the broker takes.
     #[serde_as(as = "Option<serde_with::DisplayFromStr>")]
-    pub commission: Option<f64>,
+    pub commission: Option<Num>,
     // TODO explain
     #[serde_as(as = "Option<serde_with::DisplayFromStr>")]
-    pub commission_bps: Option<f64>,
+    pub commission_bps: Option<Num>,
     // TODO explain [no official explanation]
     pub source: Option<String>,
     // TODO explain [no official explanation]
@@ -43,3 +54,35 @@ pub struct CreateOrderBroker {
     // TODO explain [no official explanation]
     pub swap_fee_bps: Option<String>,
 }
+
+impl CreateOrderBroker {
+    /// Checks the order for combinations of parameters that Alpaca would reject, without sending
+    /// anything.
+    pub fn validate(&self) -> Result<()> {
+        crate::api::trading::OrderLegs {
+            order_class: self.order_class,
+            side: self.side,
+            amount: &self.amount,
+            kind: &self.kind,
+            time_in_force: self.time_in_force,
+            extended_hours: self.extended_hours,
+            take_profit: self.take_profit.as_ref(),
+            stop_loss: self.stop_loss.as_ref(),
+            legs: self.legs.as_deref(),
+        }
+        .validate()
+    }
+}
+
+impl Serialize for CreateOrderBroker {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.validate().map_err(serde::ser::Error::custom)?;
+        CreateOrderBroker::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CreateOrderBroker {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        CreateOrderBroker::deserialize(deserializer)
+    }
+}
@@ -6,8 +6,12 @@ use std::ops::RangeBounds;
 
 #[cfg(feature = "market-data-live")]
 pub mod live;
+mod crypto;
+mod options;
 mod stock;
 
+pub use crypto::*;
+pub use options::*;
 pub use stock::*;
 
 // No API /version because its different on some endpoints
@@ -30,8 +34,32 @@ pub struct GetCalendar {
 
 impl MarketDataClient {
     pub fn new(auth: TradingAuth, base_url: Url) -> Self {
-        Self(HttpClient::new_with(TraderMiddleware(auth)).with_base_url(base_url))
+        Self(HttpClient::new_with(TraderMiddleware::new(auth)).with_base_url(base_url))
     }
+
+    fn map_middleware(self, f: impl FnOnce(TraderMiddleware) -> TraderMiddleware) -> Self {
+        Self(
+            HttpClient::new_with(f(self.0.get_middleware().clone()))
+                .with_base_url(self.0.base_url().cloned().unwrap()),
+        )
+    }
+
+    /// Configures how this client handles the rate limit, see [`RateLimit`].
+    pub fn with_rate_limit(self, rate_limit: RateLimit) -> Self {
+        self.map_middleware(|middleware| TraderMiddleware {
+            rate_limiter: RateLimiter::new(rate_limit),
+            ..middleware
+        })
+    }
+
+    /// Configures how this client retries failed requests, see [`RetryPolicy`].
+    pub fn with_retry_policy(self, retry: RetryPolicy) -> Self {
+        self.map_middleware(|middleware| TraderMiddleware {
+            retry,
+            ..middleware
+        })
+    }
+
     pub fn new_live(auth: TradingAuth) -> Self {
         Self::new(auth, MARKET_PROD.parse().unwrap())
     }
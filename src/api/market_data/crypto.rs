@@ -0,0 +1,155 @@
+use super::*;
+use crate::pagination::impl_page_token_pagination;
+
+/// The crypto venue whose market data to get, which is part of the URLs of the crypto endpoints.
+#[derive(
+    Default, Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, derive_more::Display,
+)]
+pub enum CryptoLocation {
+    /// Alpaca US
+    #[default]
+    #[serde(rename = "us")]
+    #[display(fmt = "us")]
+    Us,
+    /// Kraken US
+    #[serde(rename = "us-1")]
+    #[display(fmt = "us-1")]
+    KrakenUs,
+    /// Kraken EU
+    #[serde(rename = "eu-1")]
+    #[display(fmt = "eu-1")]
+    KrakenEu,
+}
+
+#[with_builder(get_crypto_historical_bars)]
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Clone, Debug, Serialize, Deserialize, Default, ClientEndpoint)]
+#[endpoint(Get(query) "/v1beta3/crypto/{loc}/bars" in MarketDataClient -> HistoricalBars)]
+pub struct GetCryptoHistoricalBars {
+    #[serde(skip_serializing)]
+    pub loc: CryptoLocation,
+    /// The symbols, in the `BTC/USD` format.
+    #[serde_as(as = "StringWithSeparator::<CommaSeparator, String>")]
+    #[required]
+    pub symbols: Vec<String>,
+    #[required]
+    pub timeframe: Timeframe,
+    pub start: Option<DateTime>,
+    pub end: Option<DateTime>,
+    pub limit: Option<i64>,
+    pub sort: Option<Sort>,
+    /// The token of the page to get, see [`PaginationEndpoint`](crate::pagination::PaginationEndpoint).
+    pub page_token: Option<String>,
+}
+
+#[with_builder(get_crypto_historical_quotes)]
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Clone, Debug, Serialize, Deserialize, Default, ClientEndpoint)]
+#[endpoint(Get(query) "/v1beta3/crypto/{loc}/quotes" in MarketDataClient -> CryptoHistoricalQuotes)]
+pub struct GetCryptoHistoricalQuotes {
+    #[serde(skip_serializing)]
+    pub loc: CryptoLocation,
+    /// The symbols, in the `BTC/USD` format.
+    #[serde_as(as = "StringWithSeparator::<CommaSeparator, String>")]
+    #[required]
+    pub symbols: Vec<String>,
+    pub start: Option<DateTime>,
+    pub end: Option<DateTime>,
+    pub limit: Option<i64>,
+    pub sort: Option<Sort>,
+    /// The token of the page to get, see [`PaginationEndpoint`](crate::pagination::PaginationEndpoint).
+    pub page_token: Option<String>,
+}
+
+#[with_builder(get_crypto_historical_trades)]
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Clone, Debug, Serialize, Deserialize, Default, ClientEndpoint)]
+#[endpoint(Get(query) "/v1beta3/crypto/{loc}/trades" in MarketDataClient -> CryptoHistoricalTrades)]
+pub struct GetCryptoHistoricalTrades {
+    #[serde(skip_serializing)]
+    pub loc: CryptoLocation,
+    /// The symbols, in the `BTC/USD` format.
+    #[serde_as(as = "StringWithSeparator::<CommaSeparator, String>")]
+    #[required]
+    pub symbols: Vec<String>,
+    pub start: Option<DateTime>,
+    pub end: Option<DateTime>,
+    pub limit: Option<i64>,
+    pub sort: Option<Sort>,
+    /// The token of the page to get, see [`PaginationEndpoint`](crate::pagination::PaginationEndpoint).
+    pub page_token: Option<String>,
+}
+
+#[with_builder(get_crypto_latest_bars)]
+#[serde_as]
+#[derive(Clone, Debug, Serialize, Deserialize, Default, ClientEndpoint)]
+#[endpoint(Get(query) "/v1beta3/crypto/{loc}/latest/bars" in MarketDataClient -> CryptoLatestBars)]
+pub struct GetCryptoLatestBars {
+    #[serde(skip_serializing)]
+    pub loc: CryptoLocation,
+    /// The symbols, in the `BTC/USD` format.
+    #[serde_as(as = "StringWithSeparator::<CommaSeparator, String>")]
+    #[required]
+    pub symbols: Vec<String>,
+}
+
+#[with_builder(get_crypto_latest_quotes)]
+#[serde_as]
+#[derive(Clone, Debug, Serialize, Deserialize, Default, ClientEndpoint)]
+#[endpoint(Get(query) "/v1beta3/crypto/{loc}/latest/quotes" in MarketDataClient -> CryptoLatestQuotes)]
+pub struct GetCryptoLatestQuotes {
+    #[serde(skip_serializing)]
+    pub loc: CryptoLocation,
+    /// The symbols, in the `BTC/USD` format.
+    #[serde_as(as = "StringWithSeparator::<CommaSeparator, String>")]
+    #[required]
+    pub symbols: Vec<String>,
+}
+
+#[with_builder(get_crypto_latest_trades)]
+#[serde_as]
+#[derive(Clone, Debug, Serialize, Deserialize, Default, ClientEndpoint)]
+#[endpoint(Get(query) "/v1beta3/crypto/{loc}/latest/trades" in MarketDataClient -> CryptoLatestTrades)]
+pub struct GetCryptoLatestTrades {
+    #[serde(skip_serializing)]
+    pub loc: CryptoLocation,
+    /// The symbols, in the `BTC/USD` format.
+    #[serde_as(as = "StringWithSeparator::<CommaSeparator, String>")]
+    #[required]
+    pub symbols: Vec<String>,
+}
+
+#[with_builder(get_crypto_latest_orderbooks)]
+#[serde_as]
+#[derive(Clone, Debug, Serialize, Deserialize, Default, ClientEndpoint)]
+#[endpoint(Get(query) "/v1beta3/crypto/{loc}/latest/orderbooks" in MarketDataClient -> CryptoLatestOrderbooks)]
+pub struct GetCryptoLatestOrderbooks {
+    #[serde(skip_serializing)]
+    pub loc: CryptoLocation,
+    /// The symbols, in the `BTC/USD` format.
+    #[serde_as(as = "StringWithSeparator::<CommaSeparator, String>")]
+    #[required]
+    pub symbols: Vec<String>,
+}
+
+#[with_builder(get_crypto_snapshots)]
+#[serde_as]
+#[derive(Clone, Debug, Serialize, Deserialize, Default, ClientEndpoint)]
+#[endpoint(Get(query) "/v1beta3/crypto/{loc}/snapshots" in MarketDataClient -> CryptoSnapshots)]
+pub struct GetCryptoSnapshots {
+    #[serde(skip_serializing)]
+    pub loc: CryptoLocation,
+    /// The symbols, in the `BTC/USD` format.
+    #[serde_as(as = "StringWithSeparator::<CommaSeparator, String>")]
+    #[required]
+    pub symbols: Vec<String>,
+}
+
+impl_page_token_pagination! {
+    GetCryptoHistoricalBars => HistoricalBars,
+    GetCryptoHistoricalQuotes => CryptoHistoricalQuotes,
+    GetCryptoHistoricalTrades => CryptoHistoricalTrades,
+}
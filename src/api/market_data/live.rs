@@ -1,7 +1,402 @@
+//! The real-time [stock](https://docs.alpaca.markets/docs/real-time-stock-pricing-data) and
+//! [crypto](https://docs.alpaca.markets/docs/real-time-crypto-pricing-data) data streams.
+//!
+//! ```rust,no_run
+//! # use alpaca_rs::{prelude::*, api::market_data::{live::{Encoding, LiveClient}, StockFeed}};
+//! # use futures::StreamExt;
+//! # async fn run() -> Result<(), AlpacaError> {
+//! let auth = TradingAuth::from_env();
+//! let mut client = LiveClient::connect_live(&auth, StockFeed::Sip, Encoding::Json).await?;
+//! client
+//!     .subscribe(&StockSubscription {
+//!         trades: vec!["AAPL".into()],
+//!         bars: vec!["*".into()],
+//!         ..Default::default()
+//!     })
+//!     .await?;
+//!
+//! while let Some(message) = client.next().await.transpose()? {
+//!     println!("{message:?}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The crypto stream works the same way, with the [`CryptoLocation`] instead of the feed. Its
+//! order book updates can be applied to an [`OrderBook`]:
+//!
+//! ```rust,no_run
+//! # use alpaca_rs::{prelude::*, api::market_data::{live::{Encoding, LiveClient, OrderBook}, CryptoLocation}};
+//! # use futures::StreamExt;
+//! # async fn run() -> Result<(), AlpacaError> {
+//! let auth = TradingAuth::from_env();
+//! let mut client = LiveClient::connect_live(&auth, CryptoLocation::Us, Encoding::Json).await?;
+//! client
+//!     .subscribe(&CryptoSubscription {
+//!         orderbooks: vec!["BTC/USD".into()],
+//!         ..Default::default()
+//!     })
+//!     .await?;
+//!
+//! let mut book = OrderBook::default();
+//! while let Some(message) = client.next().await.transpose()? {
+//!     if let CryptoStreamMessage::Orderbook(update) = message {
+//!         book.apply(&update.data);
+//!         println!("{:?} / {:?}", book.best_bid(), book.best_ask());
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! A [`LiveClient`] ends once its connection is lost, a [`ReconnectingClient`] reconnects instead
+//! and reports the [`Gap`] of messages that were missed in the meantime.
 use super::*;
-use async_tungstenite::WebSocketStream;
+use async_tungstenite::{
+    async_std::{connect_async, ConnectStream},
+    tungstenite::{client::IntoClientRequest, http::HeaderValue, Message},
+    WebSocketStream,
+};
+use futures::{
+    io::{AsyncRead, AsyncWrite},
+    SinkExt, Stream, StreamExt,
+};
+use serde::de::DeserializeOwned;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
 
-pub struct LiveClient<S> {
+mod orderbook;
+mod reconnect;
+
+pub use orderbook::*;
+pub use reconnect::*;
+
+/// The live url of the real-time data streams, without the path of the stream.
+const STREAM_PROD: &str = "wss://stream.data.alpaca.markets/";
+/// The sandbox url of the real-time data streams, without the path of the stream.
+const STREAM_SANDBOX: &str = "wss://stream.data.sandbox.alpaca.markets/";
+
+/// A real-time data stream to connect to, e.g. a [`StockFeed`] or a [`CryptoLocation`].
+pub trait LiveFeed {
+    /// The messages sent by the stream.
+    type Message: LiveMessage;
+
+    /// The path of the stream, relative to the host of the streams.
+    fn path(self) -> String;
+}
+
+impl LiveFeed for StockFeed {
+    type Message = StockStreamMessage;
+
+    fn path(self) -> String {
+        format!("v2/{}", self.as_str())
+    }
+}
+
+impl LiveFeed for CryptoLocation {
+    type Message = CryptoStreamMessage;
+
+    fn path(self) -> String {
+        format!("v1beta3/crypto/{self}")
+    }
+}
+
+/// A message of a real-time data stream, e.g. a [`StockStreamMessage`].
+pub trait LiveMessage: DeserializeOwned + Debug + Send + Unpin + 'static {
+    /// The subscription to the channels of the stream.
+    type Subscription: Subscription;
+
+    /// The message as a control message, or [`None`] if it is market data.
+    fn control(&self) -> Option<Control<'_, Self::Subscription>>;
+
+    /// The time of the market data as set by the exchange, or [`None`] if it is a control
+    /// message.
+    fn timestamp(&self) -> Option<DateTime>;
+}
+
+/// The subscription to the channels of a real-time data stream, e.g. a [`StockSubscription`].
+pub trait Subscription: Serialize + Clone + Default + Debug + Send + Unpin + 'static {
+    /// Whether no channel has any symbols.
+    fn is_empty(&self) -> bool;
+    /// Adds the symbols of each channel of `other` to this subscription.
+    fn extend(&mut self, other: &Self);
+    /// Removes the symbols of each channel of `other` from this subscription.
+    fn remove(&mut self, other: &Self);
+}
+
+/// A message of a real-time data stream that is not market data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control<'a, T> {
+    /// The connection or the authentication succeeded.
+    Success(&'a str),
+    /// Something went wrong, e.g. the authentication failed or a subscription is invalid.
+    Error { code: u32, msg: &'a str },
+    /// The current subscription, sent after each (un)subscribe.
+    Subscription(&'a T),
+}
+
+macro_rules! impl_live_message {
+    ($($message:ident => $subscription:ident [$($data:ident),*]),* $(,)?) => {$(
+        impl LiveMessage for $message {
+            type Subscription = $subscription;
+
+            fn control(&self) -> Option<Control<'_, $subscription>> {
+                match self {
+                    Self::Success { msg } => Some(Control::Success(msg)),
+                    Self::Error { code, msg } => Some(Control::Error { code: *code, msg }),
+                    Self::Subscription(subscription) => Some(Control::Subscription(subscription)),
+                    _ => None,
+                }
+            }
+
+            fn timestamp(&self) -> Option<DateTime> {
+                match self {
+                    $(Self::$data(data) => Some(data.data.timestamp),)*
+                    _ => None,
+                }
+            }
+        }
+
+        impl Subscription for $subscription {
+            fn is_empty(&self) -> bool {
+                $subscription::is_empty(self)
+            }
+
+            fn extend(&mut self, other: &Self) {
+                $subscription::extend(self, other)
+            }
+
+            fn remove(&mut self, other: &Self) {
+                $subscription::remove(self, other)
+            }
+        }
+    )*};
+}
+
+impl_live_message! {
+    StockStreamMessage => StockSubscription
+        [Trade, Quote, Bar, DailyBar, UpdatedBar, Status, Luld],
+    CryptoStreamMessage => CryptoSubscription
+        [Trade, Quote, Bar, DailyBar, UpdatedBar, Orderbook],
+}
+
+#[derive(Serialize)]
+#[serde(tag = "action", rename_all = "lowercase")]
+enum Action<'a, T> {
+    Auth { key: &'a str, secret: &'a str },
+    Subscribe(&'a T),
+    Unsubscribe(&'a T),
+}
+
+/// The encoding of the messages of the stream, chosen when connecting.
+///
+/// Both encodings decode into the same messages.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    #[default]
+    Json,
+    /// MessagePack, which is much cheaper to decode than JSON at high message rates.
+    MessagePack,
+}
+
+impl Encoding {
+    fn content_type(self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::MessagePack => "application/msgpack",
+        }
+    }
+
+    /// Decodes a frame of the stream, which is an array of messages.
+    pub fn decode<M: LiveMessage>(self, frame: &[u8]) -> Result<Vec<M>> {
+        Ok(match self {
+            Self::Json => serde_json::from_slice(frame)?,
+            Self::MessagePack => rmp_serde::from_slice(frame)?,
+        })
+    }
+
+    fn encode<T: Serialize>(self, action: &Action<'_, T>) -> Result<Message> {
+        Ok(match self {
+            Self::Json => Message::Text(serde_json::to_string(action)?),
+            // the messages are maps, not the compact array form of structs
+            Self::MessagePack => Message::Binary(rmp_serde::to_vec_named(action)?),
+        })
+    }
+}
+
+/// A client of a real-time data stream, of stock data unless stated otherwise.
+///
+/// The messages of the stream are received by using the client as a [`Stream`].
+#[must_use = "A client does not receive anything unless it is polled as a stream"]
+pub struct LiveClient<M: LiveMessage = StockStreamMessage, S = ConnectStream> {
     pub socket: WebSocketStream<S>,
     pub base_url: Url,
+    encoding: Encoding,
+    subscription: M::Subscription,
+    messages: VecDeque<M>,
+}
+
+/// A client of the real-time crypto data stream.
+pub type CryptoLiveClient<S = ConnectStream> = LiveClient<CryptoStreamMessage, S>;
+
+impl<M: LiveMessage> LiveClient<M> {
+    /// Connects to the live stream of the given feed, and authenticates.
+    pub async fn connect_live<F: LiveFeed<Message = M>>(
+        auth: &TradingAuth,
+        feed: F,
+        encoding: Encoding,
+    ) -> Result<Self> {
+        Self::connect(
+            auth,
+            format!("{STREAM_PROD}{}", feed.path()).parse().unwrap(),
+            encoding,
+        )
+        .await
+    }
+
+    /// Connects to the sandbox stream of the given feed, and authenticates.
+    pub async fn connect_sandbox<F: LiveFeed<Message = M>>(
+        auth: &TradingAuth,
+        feed: F,
+        encoding: Encoding,
+    ) -> Result<Self> {
+        Self::connect(
+            auth,
+            format!("{STREAM_SANDBOX}{}", feed.path()).parse().unwrap(),
+            encoding,
+        )
+        .await
+    }
+
+    /// Connects to the stream at `base_url` (which includes the feed), and authenticates.
+    pub async fn connect(auth: &TradingAuth, base_url: Url, encoding: Encoding) -> Result<Self> {
+        let mut request = base_url.as_str().into_client_request()?;
+        request.headers_mut().insert(
+            "Content-Type",
+            HeaderValue::from_static(encoding.content_type()),
+        );
+        let (socket, _) = connect_async(request).await?;
+        Self::new(socket, base_url, encoding, auth).await
+    }
+}
+
+impl<M: LiveMessage, S: AsyncRead + AsyncWrite + Unpin> LiveClient<M, S> {
+    /// Authenticates on an already connected socket.
+    ///
+    /// This allows using the stream with any async runtime or transport, the `connect` methods
+    /// connect with `async-std`. For MessagePack, the socket has to be connected with the
+    /// `Content-Type: application/msgpack` header.
+    pub async fn new(
+        socket: WebSocketStream<S>,
+        base_url: Url,
+        encoding: Encoding,
+        auth: &TradingAuth,
+    ) -> Result<Self> {
+        let mut client = Self {
+            socket,
+            base_url,
+            encoding,
+            subscription: M::Subscription::default(),
+            messages: VecDeque::new(),
+        };
+
+        client.expect_success("connected").await?;
+        client
+            .send(&Action::Auth {
+                key: &auth.key_id,
+                secret: &auth.secret,
+            })
+            .await?;
+        client.expect_success("authenticated").await?;
+
+        Ok(client)
+    }
+
+    /// The encoding of the messages of the stream.
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// The current subscription, as last confirmed by the stream.
+    pub fn subscription(&self) -> &M::Subscription {
+        &self.subscription
+    }
+
+    /// Subscribes to more symbols, in addition to the current subscription.
+    ///
+    /// The stream confirms the subscription with a [`Control::Subscription`] message, which is
+    /// when [`LiveClient::subscription`] changes.
+    pub async fn subscribe(&mut self, subscription: &M::Subscription) -> Result<()> {
+        self.send(&Action::Subscribe(subscription)).await
+    }
+
+    /// Unsubscribes from symbols of the current subscription.
+    ///
+    /// The stream confirms the subscription with a [`Control::Subscription`] message, which is
+    /// when [`LiveClient::subscription`] changes.
+    pub async fn unsubscribe(&mut self, subscription: &M::Subscription) -> Result<()> {
+        self.send(&Action::Unsubscribe(subscription)).await
+    }
+
+    async fn send(&mut self, action: &Action<'_, M::Subscription>) -> Result<()> {
+        self.socket.send(self.encoding.encode(action)?).await?;
+        Ok(())
+    }
+
+    async fn expect_success(&mut self, expected: &str) -> Result<()> {
+        let Some(message) = self.next().await.transpose()? else {
+            return Err(Error::Stream {
+                code: None,
+                message: format!("expected \"{expected}\", but the stream was closed"),
+            });
+        };
+
+        match message.control() {
+            Some(Control::Success(msg)) if msg == expected => Ok(()),
+            Some(Control::Error { code, msg }) => Err(Error::Stream {
+                code: Some(code),
+                message: msg.to_owned(),
+            }),
+            _ => Err(Error::Stream {
+                code: None,
+                message: format!("expected \"{expected}\", got {message:?}"),
+            }),
+        }
+    }
+}
+
+impl<M: LiveMessage, S: AsyncRead + AsyncWrite + Unpin> Stream for LiveClient<M, S> {
+    type Item = Result<M>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(message) = self.messages.pop_front() {
+                if let Some(Control::Subscription(subscription)) = message.control() {
+                    self.subscription = subscription.clone();
+                }
+                return Poll::Ready(Some(Ok(message)));
+            }
+
+            let frame = match ready!(self.socket.poll_next_unpin(cx)) {
+                Some(Ok(frame)) => frame,
+                Some(Err(error)) => return Poll::Ready(Some(Err(error.into()))),
+                None => return Poll::Ready(None),
+            };
+
+            let messages = match frame {
+                Message::Text(text) => Encoding::Json.decode(text.as_bytes()),
+                Message::Binary(binary) => Encoding::MessagePack.decode(&binary),
+                Message::Close(_) => return Poll::Ready(None),
+                // pings are answered by tungstenite itself
+                _ => continue,
+            };
+
+            match messages {
+                Ok(messages) => self.messages.extend(messages),
+                Err(error) => return Poll::Ready(Some(Err(error))),
+            }
+        }
+    }
 }
@@ -0,0 +1,129 @@
+use super::*;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+/// A price level of an [`OrderBook`], ordered by the total order of `f64`.
+#[derive(Debug, Clone, Copy)]
+struct Price(f64);
+
+impl PartialEq for Price {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Price {}
+
+impl PartialOrd for Price {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Price {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// The order book of a crypto symbol, kept up to date by applying the
+/// [`CryptoStreamMessage::Orderbook`] updates of the real-time crypto data stream.
+///
+/// The stream sends a snapshot of the whole book after subscribing (also after reconnecting), and
+/// incremental updates of single price levels after that.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OrderBook {
+    bids: BTreeMap<Price, f64>,
+    asks: BTreeMap<Price, f64>,
+    timestamp: Option<DateTime>,
+}
+
+impl OrderBook {
+    /// Applies an update of the stream. A snapshot replaces the whole book, otherwise the size of
+    /// each price level is updated, a size of `0` removing the level.
+    pub fn apply(&mut self, update: &CryptoOrderbookUpdate) {
+        if update.reset {
+            self.bids.clear();
+            self.asks.clear();
+        }
+        Self::update_levels(&mut self.bids, &update.bids);
+        Self::update_levels(&mut self.asks, &update.asks);
+        self.timestamp = Some(update.timestamp);
+    }
+
+    fn update_levels(levels: &mut BTreeMap<Price, f64>, entries: &[OrderbookEntry]) {
+        for entry in entries {
+            if entry.size == 0.0 {
+                levels.remove(&Price(entry.price));
+            } else {
+                levels.insert(Price(entry.price), entry.size);
+            }
+        }
+    }
+
+    /// The time of the last applied update, or [`None`] if no update has been applied yet.
+    pub fn timestamp(&self) -> Option<DateTime> {
+        self.timestamp
+    }
+
+    /// The bids, highest price first.
+    pub fn bids(&self) -> impl Iterator<Item = OrderbookEntry> + '_ {
+        self.bids.iter().rev().map(|(price, &size)| OrderbookEntry {
+            price: price.0,
+            size,
+        })
+    }
+
+    /// The asks, lowest price first.
+    pub fn asks(&self) -> impl Iterator<Item = OrderbookEntry> + '_ {
+        self.asks.iter().map(|(price, &size)| OrderbookEntry {
+            price: price.0,
+            size,
+        })
+    }
+
+    /// The bid with the highest price.
+    pub fn best_bid(&self) -> Option<OrderbookEntry> {
+        self.bids().next()
+    }
+
+    /// The ask with the lowest price.
+    pub fn best_ask(&self) -> Option<OrderbookEntry> {
+        self.asks().next()
+    }
+
+    /// The difference between the best ask and the best bid price.
+    pub fn spread(&self) -> Option<f64> {
+        Some(self.best_ask()?.price - self.best_bid()?.price)
+    }
+
+    /// The price in the middle of the best bid and the best ask.
+    pub fn mid_price(&self) -> Option<f64> {
+        Some((self.best_ask()?.price + self.best_bid()?.price) / 2.0)
+    }
+
+    /// The total size of the best `levels` price levels of the bids and of the asks.
+    pub fn depth(&self, levels: usize) -> (f64, f64) {
+        (
+            self.bids().take(levels).map(|entry| entry.size).sum(),
+            self.asks().take(levels).map(|entry| entry.size).sum(),
+        )
+    }
+
+    /// Whether the book has no price levels at all, e.g. before the first snapshot.
+    pub fn is_empty(&self) -> bool {
+        self.bids.is_empty() && self.asks.is_empty()
+    }
+}
+
+/// A book from the latest order book of a symbol, as returned by
+/// [`GetCryptoLatestOrderbooks`](crate::api::market_data::GetCryptoLatestOrderbooks).
+impl From<&CryptoOrderbook> for OrderBook {
+    fn from(orderbook: &CryptoOrderbook) -> Self {
+        let mut book = Self::default();
+        Self::update_levels(&mut book.bids, &orderbook.bids);
+        Self::update_levels(&mut book.asks, &orderbook.asks);
+        book.timestamp = Some(orderbook.timestamp);
+        book
+    }
+}
@@ -0,0 +1,202 @@
+use super::*;
+use crate::api::retry::RetryPolicy;
+use futures::future::BoxFuture;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// A period of time in which the stream was disconnected, so its messages were missed.
+///
+/// The missed data can be backfilled with the historical endpoints, e.g.
+/// [`GetHistoricalBars`](crate::api::market_data::GetHistoricalBars).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Gap {
+    /// The exchange time of the last market data received before the connection was lost, or
+    /// the time of connecting if none was received.
+    pub from: DateTime,
+    /// The time the subscription was replayed on the new connection, from which on the
+    /// messages are received again.
+    pub to: DateTime,
+}
+
+/// An event of a [`ReconnectingClient`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum LiveEvent<M = StockStreamMessage> {
+    /// A message of the stream.
+    Message(M),
+    /// The connection was lost and has been re-established, which includes authenticating and
+    /// subscribing to the current subscription again.
+    Reconnected(Gap),
+}
+
+enum State<M: LiveMessage> {
+    Connected(LiveClient<M>),
+    /// Resolves to the new client and the time the subscription was replayed.
+    Reconnecting(BoxFuture<'static, Result<(LiveClient<M>, DateTime)>>),
+    Failed,
+}
+
+/// A [`LiveClient`] that reconnects whenever the connection is lost, with a backoff between the
+/// attempts.
+///
+/// After reconnecting, it authenticates and subscribes to the current subscription again, and
+/// emits a [`LiveEvent::Reconnected`] with the [`Gap`] of the missed messages. Once the attempts
+/// run out, the stream yields the last error and ends.
+///
+/// For the crypto stream, order books have to be reset after reconnecting, which happens on its
+/// own with [`OrderBook::apply`], as the stream sends a snapshot of each book after subscribing.
+#[must_use = "A client does not receive anything unless it is polled as a stream"]
+pub struct ReconnectingClient<M: LiveMessage = StockStreamMessage> {
+    auth: TradingAuth,
+    base_url: Url,
+    encoding: Encoding,
+    backoff: RetryPolicy,
+    subscription: Arc<Mutex<M::Subscription>>,
+    connected_at: DateTime,
+    /// The latest exchange time of the market data received so far.
+    last_data: Option<DateTime>,
+    state: State<M>,
+}
+
+impl<M: LiveMessage> ReconnectingClient<M> {
+    /// Connects to the stream at `base_url` (which includes the feed), and authenticates.
+    pub async fn connect(auth: TradingAuth, base_url: Url, encoding: Encoding) -> Result<Self> {
+        let client = LiveClient::connect(&auth, base_url, encoding).await?;
+        Ok(Self::new(client, auth))
+    }
+
+    /// Supervises an already connected client, `auth` is used to authenticate again after
+    /// reconnecting.
+    pub fn new(client: LiveClient<M>, auth: TradingAuth) -> Self {
+        Self {
+            auth,
+            base_url: client.base_url.clone(),
+            encoding: client.encoding(),
+            backoff: RetryPolicy {
+                max_attempts: 10,
+                initial_backoff: Duration::from_secs(1),
+                max_backoff: Duration::from_secs(60),
+                jitter: true,
+            },
+            subscription: Arc::new(Mutex::new(client.subscription().clone())),
+            connected_at: Utc::now(),
+            last_data: None,
+            state: State::Connected(client),
+        }
+    }
+
+    /// Configures the backoff between reconnection attempts, and how many attempts are made after
+    /// the connection is lost.
+    pub fn with_backoff(mut self, backoff: RetryPolicy) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// The current subscription, which is replayed after reconnecting.
+    pub fn subscription(&self) -> M::Subscription {
+        self.subscription.lock().unwrap().clone()
+    }
+
+    /// Subscribes to more symbols, in addition to the current subscription.
+    ///
+    /// While reconnecting, the subscription is only sent once the connection is re-established.
+    pub async fn subscribe(&mut self, subscription: &M::Subscription) -> Result<()> {
+        self.subscription.lock().unwrap().extend(subscription);
+        match &mut self.state {
+            State::Connected(client) => client.subscribe(subscription).await,
+            _ => Ok(()),
+        }
+    }
+
+    /// Unsubscribes from symbols of the current subscription.
+    ///
+    /// While reconnecting, this only takes effect once the connection is re-established.
+    pub async fn unsubscribe(&mut self, subscription: &M::Subscription) -> Result<()> {
+        self.subscription.lock().unwrap().remove(subscription);
+        match &mut self.state {
+            State::Connected(client) => client.unsubscribe(subscription).await,
+            _ => Ok(()),
+        }
+    }
+
+    fn reconnect(&mut self) {
+        let auth = self.auth.clone();
+        let base_url = self.base_url.clone();
+        let encoding = self.encoding;
+        let backoff = self.backoff;
+        let subscription = self.subscription.clone();
+
+        self.state = State::Reconnecting(Box::pin(async move {
+            let mut attempt = 0;
+            loop {
+                attempt += 1;
+                async_std::task::sleep(backoff.backoff(attempt)).await;
+
+                let result = async {
+                    let mut client = LiveClient::connect(&auth, base_url.clone(), encoding).await?;
+                    let subscription = subscription.lock().unwrap().clone();
+                    if !subscription.is_empty() {
+                        client.subscribe(&subscription).await?;
+                    }
+                    Ok::<_, Error>((client, Utc::now()))
+                }
+                .await;
+
+                match result {
+                    Ok(reconnected) => return Ok(reconnected),
+                    Err(error) if attempt < backoff.max_attempts => warn!(
+                        "Reconnecting to the stream failed ({error}), attempt {attempt} of {}",
+                        backoff.max_attempts
+                    ),
+                    Err(error) => return Err(error),
+                }
+            }
+        }));
+    }
+}
+
+impl<M: LiveMessage> Stream for ReconnectingClient<M> {
+    type Item = Result<LiveEvent<M>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            match &mut this.state {
+                State::Connected(client) => match ready!(client.poll_next_unpin(cx)) {
+                    Some(Ok(message)) => {
+                        this.last_data = this.last_data.max(message.timestamp());
+                        return Poll::Ready(Some(Ok(LiveEvent::Message(message))));
+                    }
+                    // a malformed message does not mean that the connection is broken
+                    Some(Err(error @ (Error::Json(_) | Error::MessagePackDecode(_)))) => {
+                        return Poll::Ready(Some(Err(error)))
+                    }
+                    Some(Err(error)) => {
+                        warn!("The stream failed ({error}), reconnecting");
+                        this.reconnect();
+                    }
+                    None => {
+                        warn!("The stream was closed, reconnecting");
+                        this.reconnect();
+                    }
+                },
+                State::Reconnecting(reconnecting) => match ready!(reconnecting.as_mut().poll(cx)) {
+                    Ok((client, replayed)) => {
+                        this.state = State::Connected(client);
+                        let gap = Gap {
+                            from: this.last_data.unwrap_or(this.connected_at),
+                            to: replayed,
+                        };
+                        return Poll::Ready(Some(Ok(LiveEvent::Reconnected(gap))));
+                    }
+                    Err(error) => {
+                        this.state = State::Failed;
+                        return Poll::Ready(Some(Err(error)));
+                    }
+                },
+                State::Failed => return Poll::Ready(None),
+            }
+        }
+    }
+}
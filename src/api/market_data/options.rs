@@ -0,0 +1,166 @@
+use super::*;
+use crate::pagination::impl_page_token_pagination;
+
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OptionFeed {
+    /// The Options Price Reporting Authority, i.e. all US options exchanges
+    Opra,
+    /// Alpaca's indicative feed, derived from OPRA, which is free but delayed
+    #[default]
+    Indicative,
+}
+
+/// The snapshots of the option contracts of an underlying symbol, i.e. its option chain.
+#[with_builder(get_option_chain)]
+#[skip_serializing_none]
+#[serde_as]
+#[derive(Clone, Debug, Serialize, Deserialize, Default, ClientEndpoint)]
+#[endpoint(Get(query) "/v1beta1/options/snapshots/{underlying_symbol}" in MarketDataClient -> OptionSnapshots)]
+pub struct GetOptionChain {
+    #[serde(skip_serializing)]
+    #[required]
+    pub underlying_symbol: String,
+    pub feed: Option<OptionFeed>,
+    pub limit: Option<i64>,
+    pub updated_since: Option<DateTime>,
+    /// Only calls or only puts.
+    #[serde(rename = "type")]
+    pub kind: Option<OptionType>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub strike_price_gte: Option<Num>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub strike_price_lte: Option<Num>,
+    pub expiration_date: Option<Date>,
+    pub expiration_date_gte: Option<Date>,
+    pub expiration_date_lte: Option<Date>,
+    /// The root symbol of the contracts, which differs from the underlying after e.g. a split.
+    pub root_symbol: Option<String>,
+    /// The token of the page to get, see [`PaginationEndpoint`](crate::pagination::PaginationEndpoint).
+    pub page_token: Option<String>,
+}
+
+#[with_builder(get_option_snapshots)]
+#[skip_serializing_none]
+#[serde_as]
+#[derive(Clone, Debug, Serialize, Deserialize, Default, ClientEndpoint)]
+#[endpoint(Get(query) "/v1beta1/options/snapshots" in MarketDataClient -> OptionSnapshots)]
+pub struct GetOptionSnapshots {
+    #[serde_as(as = "StringWithSeparator::<CommaSeparator, String>")]
+    #[required]
+    pub symbols: Vec<String>,
+    pub feed: Option<OptionFeed>,
+    pub limit: Option<i64>,
+    pub updated_since: Option<DateTime>,
+    /// The token of the page to get, see [`PaginationEndpoint`](crate::pagination::PaginationEndpoint).
+    pub page_token: Option<String>,
+}
+
+#[with_builder(get_option_historical_bars)]
+#[skip_serializing_none]
+#[serde_as]
+#[derive(Clone, Debug, Serialize, Deserialize, Default, ClientEndpoint)]
+#[endpoint(Get(query) "/v1beta1/options/bars" in MarketDataClient -> HistoricalBars)]
+pub struct GetOptionHistoricalBars {
+    #[serde_as(as = "StringWithSeparator::<CommaSeparator, String>")]
+    #[required]
+    pub symbols: Vec<String>,
+    #[required]
+    pub timeframe: Timeframe,
+    pub start: Option<DateTime>,
+    pub end: Option<DateTime>,
+    pub limit: Option<i64>,
+    pub sort: Option<Sort>,
+    /// The token of the page to get, see [`PaginationEndpoint`](crate::pagination::PaginationEndpoint).
+    pub page_token: Option<String>,
+}
+
+#[with_builder(get_option_historical_trades)]
+#[skip_serializing_none]
+#[serde_as]
+#[derive(Clone, Debug, Serialize, Deserialize, Default, ClientEndpoint)]
+#[endpoint(Get(query) "/v1beta1/options/trades" in MarketDataClient -> OptionHistoricalTrades)]
+pub struct GetOptionHistoricalTrades {
+    #[serde_as(as = "StringWithSeparator::<CommaSeparator, String>")]
+    #[required]
+    pub symbols: Vec<String>,
+    pub start: Option<DateTime>,
+    pub end: Option<DateTime>,
+    pub limit: Option<i64>,
+    pub sort: Option<Sort>,
+    /// The token of the page to get, see [`PaginationEndpoint`](crate::pagination::PaginationEndpoint).
+    pub page_token: Option<String>,
+}
+
+#[with_builder(get_option_latest_trades)]
+#[skip_serializing_none]
+#[serde_as]
+#[derive(Clone, Debug, Serialize, Deserialize, Default, ClientEndpoint)]
+#[endpoint(Get(query) "/v1beta1/options/trades/latest" in MarketDataClient -> OptionLatestTrades)]
+pub struct GetOptionLatestTrades {
+    #[serde_as(as = "StringWithSeparator::<CommaSeparator, String>")]
+    #[required]
+    pub symbols: Vec<String>,
+    pub feed: Option<OptionFeed>,
+}
+
+#[with_builder(get_option_latest_quotes)]
+#[skip_serializing_none]
+#[serde_as]
+#[derive(Clone, Debug, Serialize, Deserialize, Default, ClientEndpoint)]
+#[endpoint(Get(query) "/v1beta1/options/quotes/latest" in MarketDataClient -> OptionLatestQuotes)]
+pub struct GetOptionLatestQuotes {
+    #[serde_as(as = "StringWithSeparator::<CommaSeparator, String>")]
+    #[required]
+    pub symbols: Vec<String>,
+    pub feed: Option<OptionFeed>,
+}
+
+/// The endpoints of the data of option contracts, which take the OCC contract symbols (e.g.
+/// `AAPL240119C00150000`) of the contracts.
+pub trait ContractSymbols: Default {
+    #[doc(hidden)]
+    fn symbols_mut(&mut self) -> &mut Vec<String>;
+
+    /// The data of the given contracts, which can be [`OptionSymbol`]s or already strings, with
+    /// the defaults for everything else.
+    ///
+    /// ```rust
+    /// # use alpaca_rs::{prelude::*, api::market_data::{ContractSymbols, GetOptionHistoricalBars}};
+    /// let call: OptionSymbol = "AAPL240119C00150000".parse().unwrap();
+    /// let bars = GetOptionHistoricalBars {
+    ///     timeframe: Timeframe::Day,
+    ///     ..GetOptionHistoricalBars::for_contracts([call])
+    /// };
+    /// ```
+    fn for_contracts(symbols: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        let mut endpoint = Self::default();
+        *endpoint.symbols_mut() = symbols.into_iter().map(Into::into).collect();
+        endpoint
+    }
+}
+
+macro_rules! impl_contract_symbols {
+    ($($endpoint:ty),* $(,)?) => {$(
+        impl ContractSymbols for $endpoint {
+            fn symbols_mut(&mut self) -> &mut Vec<String> {
+                &mut self.symbols
+            }
+        }
+    )*};
+}
+
+impl_contract_symbols! {
+    GetOptionSnapshots,
+    GetOptionHistoricalBars,
+    GetOptionHistoricalTrades,
+    GetOptionLatestTrades,
+    GetOptionLatestQuotes,
+}
+
+impl_page_token_pagination! {
+    GetOptionChain => OptionSnapshots,
+    GetOptionSnapshots => OptionSnapshots,
+    GetOptionHistoricalBars => HistoricalBars,
+    GetOptionHistoricalTrades => OptionHistoricalTrades,
+}
@@ -1,4 +1,5 @@
 use super::*;
+use crate::pagination::impl_page_token_pagination;
 
 #[derive(Default, Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -14,6 +15,18 @@ pub enum StockFeed {
     OverTheCounter,
 }
 
+impl StockFeed {
+    /// The name of the feed, as used in the URLs of the real-time data stream.
+    #[cfg(feature = "market-data-live")]
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Sip => "sip",
+            Self::InvestorsExchange => "iex",
+            Self::OverTheCounter => "otc",
+        }
+    }
+}
+
 #[with_builder(get_historical_auctions)]
 #[skip_serializing_none]
 #[serde_as]
@@ -30,6 +43,8 @@ pub struct GetHistoricalAuctions {
     pub feed: StockFeed,
     pub currency: Option<String>,
     pub sort: Option<Sort>,
+    /// The token of the page to get, see [`PaginationEndpoint`](crate::pagination::PaginationEndpoint).
+    pub page_token: Option<String>,
 }
 
 #[with_builder(get_historical_bars)]
@@ -51,6 +66,8 @@ pub struct GetHistoricalBars {
     pub feed: StockFeed,
     pub currency: Option<String>,
     pub sort: Option<Sort>,
+    /// The token of the page to get, see [`PaginationEndpoint`](crate::pagination::PaginationEndpoint).
+    pub page_token: Option<String>,
 }
 
 #[with_builder(get_latest_bars)]
@@ -97,6 +114,8 @@ pub struct GetHistoricalQuotes {
     pub asof: Option<DateTime>,
     pub feed: StockFeed,
     pub sort: Option<Sort>,
+    /// The token of the page to get, see [`PaginationEndpoint`](crate::pagination::PaginationEndpoint).
+    pub page_token: Option<String>,
 }
 
 #[with_builder(get_latest_quotes)]
@@ -141,6 +160,8 @@ pub struct GetHistoricalTrades {
     pub feed: Option<StockFeed>,
     pub currency: Option<String>,
     pub sort: Option<Sort>,
+    /// The token of the page to get, see [`PaginationEndpoint`](crate::pagination::PaginationEndpoint).
+    pub page_token: Option<String>,
 }
 
 #[with_builder(get_latest_trades)]
@@ -155,3 +176,10 @@ pub struct GetLatestTrades {
     pub feed: Option<StockFeed>,
     pub currency: Option<String>,
 }
+
+impl_page_token_pagination! {
+    GetHistoricalAuctions => HistoricalAuctions,
+    GetHistoricalBars => HistoricalBars,
+    GetHistoricalQuotes => HistoricalQuotes,
+    GetHistoricalTrades => HistoricalTrades,
+}
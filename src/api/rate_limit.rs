@@ -0,0 +1,200 @@
+//! Handling of Alpaca's [rate limit](https://docs.alpaca.markets/docs/api-rate-limit).
+//!
+//! Alpaca tells how many requests are left in the current window with the `X-RateLimit-Remaining`
+//! and `X-RateLimit-Reset` headers, and responds with `429 Too Many Requests` once the limit is
+//! exceeded. Every client keeps track of these headers, waits for the window to reset instead of
+//! sending requests that would get rejected, and retries requests that got rejected anyway.
+use super::*;
+use chrono::TimeZone;
+use retry::RetryPolicy;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// The configuration of how a client handles the rate limit.
+///
+/// Use it with the `with_rate_limit` method of a client, for example
+/// [`TradingClient::with_rate_limit`](crate::api::trading::TradingClient::with_rate_limit).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RateLimit {
+    /// Whether to wait for the rate limit window to reset before sending a request, once the
+    /// remaining requests are down to [`RateLimit::reserve`].
+    pub throttle: bool,
+    /// The number of remaining requests to keep in reserve, e.g. for other processes using the
+    /// same API key.
+    pub reserve: u32,
+    /// The maximum number of times a request is retried after being rate limited.
+    pub max_retries: u32,
+    /// How long to wait before retrying a rate limited request, if the response does not say when
+    /// the rate limit resets.
+    pub fallback_delay: Duration,
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        Self {
+            throttle: true,
+            reserve: 0,
+            max_retries: 3,
+            fallback_delay: Duration::from_secs(1),
+        }
+    }
+}
+
+impl RateLimit {
+    /// Neither throttles nor retries requests, a rate limited request fails with an
+    /// [`Error::Api`] (see [`Error::is_rate_limited`]).
+    pub fn disabled() -> Self {
+        Self {
+            throttle: false,
+            max_retries: 0,
+            ..Default::default()
+        }
+    }
+}
+
+/// The requests remaining in the current rate limit window.
+#[derive(Debug, Clone, Copy)]
+struct Budget {
+    remaining: u32,
+    reset: DateTime,
+}
+
+/// The rate limit state of a client, shared between the client and its views (e.g.
+/// [`AccountView`](crate::api::broker::AccountView)s), as they share the same limit.
+#[derive(Debug, Clone)]
+pub(crate) struct RateLimiter {
+    config: RateLimit,
+    budget: Arc<Mutex<Option<Budget>>>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(config: RateLimit) -> Self {
+        Self {
+            config,
+            budget: Arc::default(),
+        }
+    }
+
+    /// Sends a request, waiting for the rate limit to reset beforehand if needed and retrying it
+    /// if it gets rate limited, or if it fails and `retry` allows retrying it.
+    pub(crate) async fn send(&self, mut request: Request, retry: &RetryPolicy) -> Result<Response> {
+        // the body can only be read once, so it has to be kept around for retries
+        let body = match request.is_empty() {
+            Some(true) => None,
+            _ => Some(request.take_body().into_bytes().await?),
+        };
+        let idempotent = RetryPolicy::is_idempotent(&request, body.as_deref());
+
+        let mut rate_limit_retries = 0;
+        let mut attempts = 0;
+        loop {
+            while let Some(delay) = self.throttle_delay() {
+                debug!("Rate limit is exhausted, waiting {delay:?} before sending the request");
+                async_std::task::sleep(delay).await;
+            }
+
+            let mut attempt = request.clone();
+            if let Some(body) = &body {
+                attempt.set_body(body.clone());
+            }
+            attempts += 1;
+            let can_retry = idempotent && attempts < retry.max_attempts;
+
+            let response = match NoMiddleware.call(attempt).await {
+                Ok(response) => response,
+                Err(error) if can_retry => {
+                    let delay = retry.backoff(attempts);
+                    warn!("Request failed ({error}), retrying in {delay:?} (attempt {attempts} of {})", retry.max_attempts);
+                    async_std::task::sleep(delay).await;
+                    continue;
+                }
+                Err(error) => return Err(error.into()),
+            };
+            self.update(&response);
+
+            if response.status() == http_types::StatusCode::TooManyRequests
+                && rate_limit_retries < self.config.max_retries
+            {
+                // a rate limited request was never processed, so it is always safe to retry
+                rate_limit_retries += 1;
+                attempts -= 1;
+                let delay = self.retry_delay(&response);
+                warn!(
+                    "Rate limited, retrying in {delay:?} (retry {rate_limit_retries} of {})",
+                    self.config.max_retries
+                );
+                async_std::task::sleep(delay).await;
+            } else if RetryPolicy::is_transient(response.status()) && can_retry {
+                let delay = retry.backoff(attempts);
+                warn!(
+                    "Request failed with {}, retrying in {delay:?} (attempt {attempts} of {})",
+                    response.status(),
+                    retry.max_attempts
+                );
+                async_std::task::sleep(delay).await;
+            } else {
+                return Ok(response);
+            }
+        }
+    }
+
+    /// How long to wait before sending a request, reserving one request from the budget if it can
+    /// be sent right away.
+    fn throttle_delay(&self) -> Option<Duration> {
+        if !self.config.throttle {
+            return None;
+        }
+
+        let mut budget = self.budget.lock().unwrap();
+        let Budget { remaining, reset } = (*budget)?;
+        let now = Utc::now();
+
+        if reset <= now {
+            *budget = None;
+            None
+        } else if remaining > self.config.reserve {
+            *budget = Some(Budget {
+                remaining: remaining - 1,
+                reset,
+            });
+            None
+        } else {
+            (reset - now).to_std().ok()
+        }
+    }
+
+    /// Updates the budget from the rate limit headers of a response.
+    fn update(&self, response: &Response) {
+        let header = |name: &str| {
+            response
+                .header(name)
+                .map(|values| values.last().as_str().trim().to_owned())
+        };
+
+        let remaining = header("X-RateLimit-Remaining").and_then(|x| x.parse().ok());
+        let reset = header("X-RateLimit-Reset")
+            .and_then(|x| x.parse().ok())
+            .and_then(|x| Utc.timestamp_opt(x, 0).single());
+
+        if let (Some(remaining), Some(reset)) = (remaining, reset) {
+            *self.budget.lock().unwrap() = Some(Budget { remaining, reset });
+        }
+    }
+
+    /// How long to wait before retrying a rate limited request.
+    fn retry_delay(&self, response: &Response) -> Duration {
+        let retry_after = response
+            .header("Retry-After")
+            .and_then(|values| values.last().as_str().trim().parse().ok())
+            .map(Duration::from_secs);
+
+        let until_reset = || {
+            let budget = (*self.budget.lock().unwrap())?;
+            (budget.reset - Utc::now()).to_std().ok()
+        };
+
+        retry_after
+            .or_else(until_reset)
+            .unwrap_or(self.config.fallback_delay)
+    }
+}
@@ -0,0 +1,84 @@
+//! Retrying requests that failed because of transient network or server errors.
+//!
+//! Only requests that can be safely sent more than once are retried: `GET` and `DELETE` requests,
+//! and `POST` requests that carry a `client_order_id`, as Alpaca refuses to create a second order
+//! with the same client order ID. To make order submission retryable, set
+//! [`CreateOrder::client_order_id`](crate::api::trading::CreateOrder::client_order_id).
+use super::*;
+use std::time::Duration;
+
+/// The configuration of how a client retries failed requests, with exponential backoff.
+///
+/// Use it with the `with_retry_policy` method of a client, for example
+/// [`TradingClient::with_retry_policy`](crate::api::trading::TradingClient::with_retry_policy).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RetryPolicy {
+    /// The maximum number of times a request is sent, including the first attempt.
+    pub max_attempts: u32,
+    /// How long to wait before the first retry, doubled for each following retry.
+    pub initial_backoff: Duration,
+    /// The upper bound of the wait between two attempts.
+    pub max_backoff: Duration,
+    /// Whether to randomize the wait between attempts (between half and all of the backoff), so
+    /// that many clients failing at once do not retry all at the same time.
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Never retries failed requests.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// How long to wait before the given retry (the first retry being `1`).
+    pub(crate) fn backoff(&self, retry: u32) -> Duration {
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+            .min(self.max_backoff);
+
+        if self.jitter {
+            backoff.mul_f64(0.5 + fastrand::f64() / 2.0)
+        } else {
+            backoff
+        }
+    }
+
+    /// Whether a request can be sent again without side effects, `body` being the request's body.
+    pub(crate) fn is_idempotent(request: &Request, body: Option<&[u8]>) -> bool {
+        match request.method() {
+            Method::Get | Method::Head | Method::Delete => true,
+            Method::Post => body
+                .and_then(|body| serde_json::from_slice::<serde_json::Value>(body).ok())
+                .is_some_and(|body| body.get("client_order_id").is_some_and(|id| !id.is_null())),
+            _ => false,
+        }
+    }
+
+    /// Whether a response status is caused by a transient server error.
+    pub(crate) fn is_transient(status: http_types::StatusCode) -> bool {
+        use http_types::StatusCode;
+
+        matches!(
+            status,
+            StatusCode::InternalServerError
+                | StatusCode::BadGateway
+                | StatusCode::ServiceUnavailable
+                | StatusCode::GatewayTimeout
+        )
+    }
+}
@@ -1,12 +1,22 @@
 use super::*;
 
+mod activities;
 mod assets;
+mod options;
 mod orders;
+mod portfolio;
 mod positions;
+#[cfg(feature = "trading-live")]
+pub mod updates;
+mod watchlists;
 
+pub use activities::*;
 pub use assets::*;
+pub use options::*;
 pub use orders::*;
+pub use portfolio::*;
 pub use positions::*;
+pub use watchlists::*;
 
 /// The production/live url for the [Trader API](https://docs.alpaca.markets/docs/trading-api).
 const TRADING_PROD: &str = "https://api.alpaca.markets/v2/";
@@ -36,7 +46,22 @@ impl TradingAuth {
     }
 }
 
-pub(crate) struct TraderMiddleware(pub(crate) TradingAuth);
+#[derive(Clone)]
+pub(crate) struct TraderMiddleware {
+    pub(crate) auth: TradingAuth,
+    pub(crate) rate_limiter: RateLimiter,
+    pub(crate) retry: RetryPolicy,
+}
+
+impl TraderMiddleware {
+    pub(crate) fn new(auth: TradingAuth) -> Self {
+        Self {
+            auth,
+            rate_limiter: RateLimiter::new(RateLimit::default()),
+            retry: RetryPolicy::default(),
+        }
+    }
+}
 
 impl Service for TraderMiddleware {
     type Error = Error;
@@ -45,24 +70,12 @@ impl Service for TraderMiddleware {
 
 impl Middleware for TraderMiddleware {
     async fn call(&self, mut request: Request) -> Result<Response, <Self as Service>::Error> {
-        request.append_header("APCA-API-KEY-ID", &self.0.key_id);
-        request.append_header("APCA-API-SECRET-KEY", &self.0.secret);
+        request.append_header("APCA-API-KEY-ID", &self.auth.key_id);
+        request.append_header("APCA-API-SECRET-KEY", &self.auth.secret);
 
         trace!("{request:?}");
 
-        Ok(match NoMiddleware.call(request).await? {
-            res if res.status().is_success() => res,
-            mut other => {
-                return Err(http_types::Error::from_str(
-                    other.status(),
-                    format!(
-                        "status was not successful: {other:?}, {}",
-                        other.body_string().await?
-                    ),
-                )
-                .into())
-            }
-        })
+        Error::for_status(self.rate_limiter.send(request, &self.retry).await?).await
     }
 }
 
@@ -80,7 +93,30 @@ impl TradingClient {
     }
 
     pub fn new(auth: TradingAuth, base_url: Url) -> Self {
-        Self(HttpClient::new_with(TraderMiddleware(auth)).with_base_url(base_url))
+        Self(HttpClient::new_with(TraderMiddleware::new(auth)).with_base_url(base_url))
+    }
+
+    fn map_middleware(self, f: impl FnOnce(TraderMiddleware) -> TraderMiddleware) -> Self {
+        Self(
+            HttpClient::new_with(f(self.0.get_middleware().clone()))
+                .with_base_url(self.0.base_url().cloned().unwrap()),
+        )
+    }
+
+    /// Configures how this client handles the rate limit, see [`RateLimit`].
+    pub fn with_rate_limit(self, rate_limit: RateLimit) -> Self {
+        self.map_middleware(|middleware| TraderMiddleware {
+            rate_limiter: RateLimiter::new(rate_limit),
+            ..middleware
+        })
+    }
+
+    /// Configures how this client retries failed requests, see [`RetryPolicy`].
+    pub fn with_retry_policy(self, retry: RetryPolicy) -> Self {
+        self.map_middleware(|middleware| TraderMiddleware {
+            retry,
+            ..middleware
+        })
     }
 
     /// Gets the account data for this trading account.
@@ -115,6 +151,18 @@ impl TradingClient {
         Ok(())
     }
 
+    /// Connects to the trade updates stream of this account, which pushes the events of its
+    /// orders (e.g. fills) as they happen. See [`updates`] for an example.
+    #[cfg(feature = "trading-live")]
+    pub async fn trade_updates(&self) -> Result<updates::TradeUpdates> {
+        let mut url = self.0.base_url().cloned().unwrap();
+        let scheme = if url.scheme() == "http" { "ws" } else { "wss" };
+        url.set_scheme(scheme).unwrap();
+        url.set_path("/stream");
+
+        updates::TradeUpdates::connect(&self.0.get_middleware().auth, url).await
+    }
+
     pub async fn execute<T: ClientEndpoint<Context = Self, Error = Error>>(
         &self,
         endpoint: T,
@@ -146,6 +194,31 @@ impl HttpClientContext for TradingClient {
 #[endpoint(Get "/account" in TradingClient -> Account)]
 pub struct GetAccount;
 
+/// Get the trading settings of the account.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash, ClientEndpoint)]
+#[endpoint(Get "/account/configurations" in TradingClient -> AccountConfigurations)]
+pub struct GetAccountConfigurations;
+
+/// Change the trading settings of the account, returning all of them.
+///
+/// Only the given settings are changed, e.g. setting `suspend_trade` blocks all new orders.
+#[with_builder(update_account_configurations)]
+#[skip_serializing_none]
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, ClientEndpoint)]
+#[endpoint(Patch(json) "/account/configurations" in TradingClient -> AccountConfigurations)]
+pub struct UpdateAccountConfigurations {
+    pub dtbp_check: Option<DayTradeCheck>,
+    pub trade_confirm_email: Option<TradeConfirmEmail>,
+    pub suspend_trade: Option<bool>,
+    pub no_shorting: Option<bool>,
+    pub fractional_trading: Option<bool>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub max_margin_multiplier: Option<u8>,
+    pub pdt_check: Option<DayTradeCheck>,
+    pub max_options_trading_level: Option<u8>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash, ClientEndpoint)]
 #[endpoint(Get "/clock" in TradingClient -> Clock)]
 pub struct GetClock;
@@ -0,0 +1,57 @@
+use super::*;
+use crate::pagination::impl_identifiable_pagination;
+
+/// List the activities of the account (e.g. fills, dividends and transfers), newest first by
+/// default.
+#[with_builder(get_account_activities)]
+#[skip_serializing_none]
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, ClientEndpoint)]
+#[endpoint(Get(query) "/account/activities" in TradingClient -> Vec<Activity>)]
+pub struct GetAccountActivities {
+    /// Only list activities of these types.
+    #[serde_as(as = "StringWithSeparator::<CommaSeparator, ActivityType>")]
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub activity_types: Vec<ActivityType>,
+    /// Only list activities of this date, can't be combined with `until` and `after`.
+    pub date: Option<Date>,
+    /// Only list activities until this timestamp (exclusive).
+    pub until: Option<DateTime>,
+    /// Only list activities after this timestamp (exclusive).
+    pub after: Option<DateTime>,
+    /// The chronological order of the response.
+    pub direction: Option<Sort>,
+    /// The maximum number of activities in the response, 100 by default and 100 at most.
+    pub page_size: Option<u32>,
+    /// The ID of the last activity of the previous page.
+    pub page_token: Option<String>,
+}
+
+/// List the activities of the account of a single type.
+#[with_builder(get_account_activities_by_type)]
+#[skip_serializing_none]
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, Clone, ClientEndpoint)]
+#[endpoint(Get(query) "/account/activities/{activity_type}" in TradingClient -> Vec<Activity>)]
+pub struct GetAccountActivitiesByType {
+    #[required]
+    #[serde(skip_serializing)]
+    pub activity_type: ActivityType,
+    /// Only list activities of this date, can't be combined with `until` and `after`.
+    pub date: Option<Date>,
+    /// Only list activities until this timestamp (exclusive).
+    pub until: Option<DateTime>,
+    /// Only list activities after this timestamp (exclusive).
+    pub after: Option<DateTime>,
+    /// The chronological order of the response.
+    pub direction: Option<Sort>,
+    /// The maximum number of activities in the response, 100 by default and 100 at most.
+    pub page_size: Option<u32>,
+    /// The ID of the last activity of the previous page.
+    pub page_token: Option<String>,
+}
+
+impl_identifiable_pagination! {
+    GetAccountActivities => Activity,
+    GetAccountActivitiesByType => Activity,
+}
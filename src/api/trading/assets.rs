@@ -1 +1,27 @@
+use super::*;
 
+/// List the assets, e.g. to build a trading universe.
+#[with_builder(get_assets)]
+#[skip_serializing_none]
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, ClientEndpoint)]
+#[endpoint(Get(query) "/assets" in TradingClient -> Vec<Asset>)]
+pub struct GetAssets {
+    pub status: Option<AssetStatus>,
+    pub asset_class: Option<AssetClass>,
+    /// Only list assets of this exchange, e.g. `NASDAQ`.
+    pub exchange: Option<String>,
+    /// Only list assets that have any of these attributes, e.g. `has_options`.
+    #[serde_as(as = "StringWithSeparator::<CommaSeparator, String>")]
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub attributes: Vec<String>,
+}
+
+/// Get a single asset by its symbol or ID.
+#[with_builder(get_asset)]
+#[derive(Serialize, Deserialize, Debug, Clone, ClientEndpoint)]
+#[endpoint(Get(empty) "/assets/{symbol_or_asset_id}" in TradingClient -> Asset)]
+pub struct GetAsset {
+    #[required]
+    pub symbol_or_asset_id: SymbolOrAssetId,
+}
@@ -0,0 +1,45 @@
+use super::*;
+use crate::pagination::impl_page_token_pagination;
+
+/// List option contracts, by default the active ones that expire before the next weekend.
+#[with_builder(get_option_contracts)]
+#[skip_serializing_none]
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, ClientEndpoint)]
+#[endpoint(Get(query) "/options/contracts" in TradingClient -> OptionContracts)]
+pub struct GetOptionContracts {
+    /// Only list contracts on these underlying symbols.
+    #[serde_as(as = "StringWithSeparator::<CommaSeparator, String>")]
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub underlying_symbols: Vec<String>,
+    /// Whether to include the deliverables of each contract.
+    pub show_deliverables: Option<bool>,
+    pub status: Option<OptionContractStatus>,
+    pub expiration_date: Option<Date>,
+    pub expiration_date_gte: Option<Date>,
+    pub expiration_date_lte: Option<Date>,
+    pub root_symbol: Option<String>,
+    /// Only calls or only puts.
+    #[serde(rename = "type")]
+    pub kind: Option<OptionType>,
+    pub style: Option<OptionStyle>,
+    pub strike_price_gte: Option<Num>,
+    pub strike_price_lte: Option<Num>,
+    /// The maximum number of contracts in the response, 100 by default and 10000 at most.
+    pub limit: Option<i64>,
+    /// The token of the page to get, see [`PaginationEndpoint`](crate::pagination::PaginationEndpoint).
+    pub page_token: Option<String>,
+}
+
+/// Get a single option contract by its symbol or ID.
+#[with_builder(get_option_contract)]
+#[derive(Serialize, Deserialize, Debug, Clone, ClientEndpoint)]
+#[endpoint(Get(empty) "/options/contracts/{symbol_or_id}" in TradingClient -> OptionContract)]
+pub struct GetOptionContract {
+    #[required]
+    pub symbol_or_id: SymbolOrAssetId,
+}
+
+impl_page_token_pagination! {
+    GetOptionContracts => OptionContracts,
+}
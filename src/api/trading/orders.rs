@@ -2,12 +2,20 @@ use super::*;
 
 #[with_builder(create_order)]
 /// Create an order.
+///
+/// The order is [validated](CreateOrder::validate) when it is serialized, so an invalid order is
+/// never sent, executing it fails with a serialization error instead.
 #[skip_serializing_none]
 #[derive(Serialize, Deserialize, Debug, Clone, ClientEndpoint)]
+// the derived implementations are only used by the ones below, which validate the order first
+#[serde(remote = "Self")]
 #[endpoint(Post(json) "/orders" in TradingClient -> Order)]
 pub struct CreateOrder {
-    /// The symbol/ticker of the stock being traded.
+    /// The symbol/ticker of the stock being traded, or the symbol of an option contract (see
+    /// [`OptionSymbol`], which converts into a [`String`]). Empty for multi-leg orders, whose
+    /// legs have the symbols instead.
     #[required]
+    #[serde(skip_serializing_if = "String::is_empty")]
     pub symbol: String,
     /// Either the quantity or the dollar amount to trade.
     #[required]
@@ -27,6 +35,277 @@ pub struct CreateOrder {
     pub client_order_id: Option<String>,
     // TODO explain
     pub order_class: OrderClass,
+    /// The take-profit leg of a bracket, OCO or OTO order.
+    pub take_profit: Option<TakeProfit>,
+    /// The stop-loss leg of a bracket, OCO or OTO order.
+    pub stop_loss: Option<StopLoss>,
+    /// The legs of a multi-leg options order.
+    pub legs: Option<Vec<OrderLeg>>,
+}
+
+impl CreateOrder {
+    fn with_legs(
+        symbol: String,
+        qty: Num,
+        side: OrderSide,
+        order_class: OrderClass,
+        take_profit: Option<TakeProfit>,
+        stop_loss: Option<StopLoss>,
+    ) -> Self {
+        Self {
+            symbol,
+            amount: OrderAmount::Quantity(qty),
+            side,
+            kind: OrderType::Market,
+            time_in_force: OrderTif::Day,
+            extended_hours: false,
+            client_order_id: None,
+            order_class,
+            take_profit,
+            stop_loss,
+            legs: None,
+        }
+    }
+
+    /// Creates a bracket order: a market entry order with a take-profit and a stop-loss exit
+    /// order, which cancel each other once either of them fills.
+    ///
+    /// The entry order can be changed afterwards, but then [`CreateOrder::validate`] should be
+    /// called again.
+    pub fn bracket(
+        symbol: impl Into<String>,
+        qty: Num,
+        side: OrderSide,
+        take_profit: TakeProfit,
+        stop_loss: StopLoss,
+    ) -> Result<Self> {
+        let order = Self::with_legs(
+            symbol.into(),
+            qty,
+            side,
+            OrderClass::Bracket,
+            Some(take_profit),
+            Some(stop_loss),
+        );
+        order.validate()?;
+        Ok(order)
+    }
+
+    /// Creates a one-cancels-other order: a take-profit limit order and a stop-loss order, once
+    /// either of them fills the other one gets canceled.
+    ///
+    /// This is used to exit an already existing position, so `side` should be the opposite of the
+    /// side of the position.
+    pub fn one_cancels_other(
+        symbol: impl Into<String>,
+        qty: Num,
+        side: OrderSide,
+        take_profit: TakeProfit,
+        stop_loss: StopLoss,
+    ) -> Result<Self> {
+        let mut order = Self::with_legs(
+            symbol.into(),
+            qty,
+            side,
+            OrderClass::OneCancelsOther,
+            Some(take_profit),
+            Some(stop_loss),
+        );
+        order.kind = OrderType::Limit {
+            limit_price: take_profit.limit_price,
+        };
+        order.validate()?;
+        Ok(order)
+    }
+
+    /// Creates a one-triggers-other order: a market entry order with either a take-profit or a
+    /// stop-loss exit order, which gets submitted once the entry order fills.
+    pub fn one_triggers_other(
+        symbol: impl Into<String>,
+        qty: Num,
+        side: OrderSide,
+        take_profit: Option<TakeProfit>,
+        stop_loss: Option<StopLoss>,
+    ) -> Result<Self> {
+        let order = Self::with_legs(
+            symbol.into(),
+            qty,
+            side,
+            OrderClass::OneTriggersOther,
+            take_profit,
+            stop_loss,
+        );
+        order.validate()?;
+        Ok(order)
+    }
+
+    /// Creates a multi-leg options order, e.g. a spread, which buys or sells `qty` units of the
+    /// legs at once, each unit consisting of the `ratio_qty` of each leg.
+    ///
+    /// `kind` is the type of the whole order (a limit price is the net price of a unit), and
+    /// `side` is ignored by Alpaca, the side of each leg counts.
+    pub fn multi_leg(qty: Num, kind: OrderType, legs: Vec<OrderLeg>) -> Result<Self> {
+        let order = Self {
+            symbol: String::new(),
+            amount: OrderAmount::Quantity(qty),
+            side: OrderSide::Buy,
+            kind,
+            time_in_force: OrderTif::Day,
+            extended_hours: false,
+            client_order_id: None,
+            order_class: OrderClass::MultiLeg,
+            take_profit: None,
+            stop_loss: None,
+            legs: Some(legs),
+        };
+        order.validate()?;
+        Ok(order)
+    }
+
+    /// Checks the order for combinations of parameters that Alpaca would reject, without sending
+    /// anything.
+    pub fn validate(&self) -> Result<()> {
+        OrderLegs {
+            order_class: self.order_class,
+            side: self.side,
+            amount: &self.amount,
+            kind: &self.kind,
+            time_in_force: self.time_in_force,
+            extended_hours: self.extended_hours,
+            take_profit: self.take_profit.as_ref(),
+            stop_loss: self.stop_loss.as_ref(),
+            legs: self.legs.as_deref(),
+        }
+        .validate()
+    }
+}
+
+impl Serialize for CreateOrder {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.validate().map_err(serde::ser::Error::custom)?;
+        CreateOrder::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CreateOrder {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        CreateOrder::deserialize(deserializer)
+    }
+}
+
+/// The fields of an order that decide whether Alpaca accepts its class and its legs, shared by
+/// [`CreateOrder`] and [`CreateOrderBroker`](crate::api::broker::CreateOrderBroker).
+pub(crate) struct OrderLegs<'a> {
+    pub order_class: OrderClass,
+    pub side: OrderSide,
+    pub amount: &'a OrderAmount,
+    pub kind: &'a OrderType,
+    pub time_in_force: OrderTif,
+    pub extended_hours: bool,
+    pub take_profit: Option<&'a TakeProfit>,
+    pub stop_loss: Option<&'a StopLoss>,
+    pub legs: Option<&'a [OrderLeg]>,
+}
+
+impl OrderLegs<'_> {
+    /// Checks the order class specific constraints of the order.
+    pub fn validate(&self) -> Result<()> {
+        let Self {
+            order_class,
+            side,
+            amount,
+            kind,
+            time_in_force,
+            extended_hours,
+            take_profit,
+            stop_loss,
+            legs,
+        } = *self;
+
+        if order_class != OrderClass::MultiLeg && legs.is_some() {
+            return Err(Error::InvalidOrder("only multi-leg orders can have legs"));
+        }
+
+        if order_class == OrderClass::Simple {
+            if take_profit.is_some() || stop_loss.is_some() {
+                return Err(Error::InvalidOrder(
+                    "simple orders cannot have take-profit or stop-loss legs",
+                ));
+            }
+            return Ok(());
+        }
+
+        if !matches!(time_in_force, OrderTif::Day | OrderTif::GoodTillCanceled) {
+            return Err(Error::InvalidOrder(
+                "advanced orders must be either day or good till canceled orders",
+            ));
+        }
+        if extended_hours {
+            return Err(Error::InvalidOrder(
+                "advanced orders cannot be processed in extended hours",
+            ));
+        }
+        if matches!(amount, OrderAmount::Notional(_)) {
+            return Err(Error::InvalidOrder(
+                "advanced orders must be placed with a quantity, not a notional amount",
+            ));
+        }
+
+        if order_class == OrderClass::MultiLeg {
+            return if take_profit.is_some() || stop_loss.is_some() {
+                Err(Error::InvalidOrder(
+                    "multi-leg orders cannot have take-profit or stop-loss legs",
+                ))
+            } else if !(1..=4).contains(&legs.map_or(0, <[OrderLeg]>::len)) {
+                Err(Error::InvalidOrder(
+                    "multi-leg orders need at least one and at most four legs",
+                ))
+            } else if legs.into_iter().flatten().any(|leg| leg.ratio_qty == 0) {
+                Err(Error::InvalidOrder(
+                    "the ratio quantity of a leg cannot be zero",
+                ))
+            } else {
+                Ok(())
+            };
+        }
+
+        if order_class == OrderClass::OneTriggersOther {
+            return if take_profit.is_some() == stop_loss.is_some() {
+                Err(Error::InvalidOrder(
+                    "one-triggers-other orders need exactly one of a take-profit or a stop-loss leg",
+                ))
+            } else {
+                Ok(())
+            };
+        }
+
+        let (Some(take_profit), Some(stop_loss)) = (take_profit, stop_loss) else {
+            return Err(Error::InvalidOrder(
+                "bracket and one-cancels-other orders need both a take-profit and a stop-loss leg",
+            ));
+        };
+
+        if order_class == OrderClass::OneCancelsOther && !matches!(kind, OrderType::Limit { .. }) {
+            return Err(Error::InvalidOrder(
+                "one-cancels-other orders must be limit orders",
+            ));
+        }
+
+        // a bracket order's legs exit the position opened by its entry order (so the opposite side of
+        // `side`), while an OCO order itself is the exit. when exiting a long position the take-profit
+        // has to be above the stop-loss, when exiting a short position it has to be below it.
+        let exits_long = match order_class {
+            OrderClass::Bracket => side == OrderSide::Buy,
+            _ => side == OrderSide::Sell,
+        };
+        if exits_long != (take_profit.limit_price > stop_loss.stop_price) {
+            return Err(Error::InvalidOrder(
+                "the take-profit limit price is on the wrong side of the stop-loss stop price",
+            ));
+        }
+
+        Ok(())
+    }
 }
 
 impl std::fmt::Display for CreateOrder {
@@ -52,10 +331,95 @@ impl std::fmt::Display for CreateOrder {
     }
 }
 
+/// List orders, by default only the open ones.
+#[with_builder(get_orders)]
+#[skip_serializing_none]
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, ClientEndpoint)]
+#[endpoint(Get(query) "/orders" in TradingClient -> Vec<Order>)]
+pub struct GetOrders {
+    /// Which orders to list (open, closed or all).
+    pub status: Option<OrderStatusFilter>,
+    /// The maximum number of orders in the response, 50 by default and 500 at most.
+    pub limit: Option<u32>,
+    /// Only list orders submitted after this timestamp (exclusive).
+    pub after: Option<DateTime>,
+    /// Only list orders submitted until this timestamp (exclusive).
+    pub until: Option<DateTime>,
+    /// The chronological order of the response, based on the submission time.
+    pub direction: Option<Sort>,
+    /// If true, the legs of multi-leg orders are rolled up under the `legs` field of the
+    /// primary order.
+    pub nested: Option<bool>,
+    /// Only list orders for these symbols.
+    #[serde_as(as = "StringWithSeparator::<CommaSeparator, String>")]
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub symbols: Vec<String>,
+}
+
+/// Get a single order by its ID.
+#[with_builder(get_order)]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, ClientEndpoint)]
+#[endpoint(Get(query) "/orders/{order_id}" in TradingClient -> Order)]
+pub struct GetOrder {
+    #[required]
+    #[serde(skip_serializing)]
+    pub order_id: String,
+    /// If true, the legs of a multi-leg order are rolled up under the `legs` field.
+    pub nested: Option<bool>,
+}
+
+/// Get a single order by the client order ID it was submitted with.
+#[with_builder(get_order_by_client_id)]
+#[derive(Serialize, Deserialize, Debug, Clone, ClientEndpoint)]
+// the `./` keeps the url parser from taking `orders:` for a scheme
+#[endpoint(Get(query) "/./orders:by_client_order_id" in TradingClient -> Order)]
+pub struct GetOrderByClientId {
+    #[required]
+    pub client_order_id: String,
+}
+
+/// Replace an open order with an updated one.
+///
+/// Alpaca creates a new order (with a new ID) and cancels the old one, the returned order is the
+/// new one.
+#[with_builder(replace_order)]
+#[skip_serializing_none]
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, Clone, ClientEndpoint)]
+#[endpoint(Patch(json) "/orders/{order_id}" in TradingClient -> Order)]
+pub struct ReplaceOrder {
+    #[required]
+    #[serde(skip_serializing)]
+    pub order_id: String,
+    /// The new number of shares to trade.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub qty: Option<Num>,
+    pub time_in_force: Option<OrderTif>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub limit_price: Option<Num>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub stop_price: Option<Num>,
+    /// The new trailing amount (a price or a percentage, depending on the original order).
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub trail: Option<Num>,
+    pub client_order_id: Option<String>,
+}
+
 #[with_builder(cancel_order)]
 #[derive(Serialize, Deserialize, Debug, Clone, ClientEndpoint)]
-#[endpoint(Delete(empty, empty) "orders/{order_id}" in TradingClient)]
+#[endpoint(Delete(empty, empty) "/orders/{order_id}" in TradingClient)]
 pub struct CancelOrder {
     #[required]
     pub order_id: String,
 }
+
+/// Cancel all open orders.
+///
+/// The response contains the result of the cancellation of each order, an order failing to cancel
+/// does not fail the whole request.
+#[with_builder(cancel_all_orders)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, ClientEndpoint)]
+#[endpoint(Delete "/orders" in TradingClient -> Vec<OrderCancellation>)]
+pub struct CancelAllOrders;
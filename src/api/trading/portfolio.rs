@@ -0,0 +1,28 @@
+use super::*;
+
+/// Get the equity and profit/loss of the account over time, by default over the last month in
+/// daily resolution.
+#[with_builder(get_portfolio_history)]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, ClientEndpoint)]
+#[endpoint(Get(query) "/account/portfolio/history" in TradingClient -> PortfolioHistory)]
+pub struct GetPortfolioHistory {
+    /// The duration of the history, a number and a unit: `D` for days, `W` for weeks, `M` for
+    /// months and `A` for years, e.g. `3M`.
+    pub period: Option<String>,
+    /// The resolution of the history. Intraday resolutions are only available for periods of
+    /// less than 30 days.
+    pub timeframe: Option<PortfolioTimeframe>,
+    /// Which hours an intraday history covers.
+    pub intraday_reporting: Option<IntradayReporting>,
+    /// The start of the history, instead of the end minus the period.
+    pub start: Option<DateTime>,
+    /// The end of the history, now by default.
+    pub end: Option<DateTime>,
+    /// The date the history ends, now by default. Prefer `end`.
+    pub date_end: Option<Date>,
+    /// If true, an intraday history includes the extended hours.
+    pub extended_hours: Option<bool>,
+    /// When the profit/loss resets to zero.
+    pub pnl_reset: Option<PnlReset>,
+}
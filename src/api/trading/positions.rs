@@ -2,20 +2,20 @@ use super::*;
 
 #[with_builder(get_open_positions)]
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, ClientEndpoint)]
-#[endpoint(Get "/v2/positions" in TradingClient -> Vec<OpenPosition>)]
+#[endpoint(Get "/positions" in TradingClient -> Vec<OpenPosition>)]
 pub struct GetOpenPositions;
 
 #[with_builder(close_all_positions)]
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize, ClientEndpoint)]
-#[endpoint(Delete(query) "/v2/positions" in TradingClient)]
+#[endpoint(Delete(query) "/positions" in TradingClient)]
 pub struct CloseAllPositions {
     pub cancel_orders: Option<bool>,
 }
 
 #[with_builder(get_open_position)]
 #[derive(Debug, Clone, Serialize, Deserialize, ClientEndpoint)]
-#[endpoint(Get(empty) "/v2/positions/{symbol_or_asset_id}" in TradingClient -> OpenPosition)]
+#[endpoint(Get(empty) "/positions/{symbol_or_asset_id}" in TradingClient -> OpenPosition)]
 pub struct GetOpenPosition {
     #[required]
     pub symbol_or_asset_id: SymbolOrAssetId,
@@ -23,12 +23,27 @@ pub struct GetOpenPosition {
 
 #[with_builder(close_position)]
 #[skip_serializing_none]
+#[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize, ClientEndpoint)]
-#[endpoint(Delete(query) "/v2/positions/{symbol_or_asset_id}" in TradingClient)]
+#[endpoint(Delete(query) "/positions/{symbol_or_asset_id}" in TradingClient)]
 pub struct ClosePosition {
     #[required]
     #[serde(skip_serializing)]
     pub symbol_or_asset_id: SymbolOrAssetId,
-    pub qty: Option<f64>,
-    pub percentage: Option<f64>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub qty: Option<Num>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub percentage: Option<Num>,
+}
+
+/// Exercise a held option contract, which cannot be undone.
+///
+/// Alpaca also exercises contracts that are in the money at the expiration on its own.
+#[with_builder(exercise_option_position)]
+#[derive(Debug, Clone, Serialize, Deserialize, ClientEndpoint)]
+#[endpoint(Post(empty, empty) "/positions/{symbol_or_contract_id}/exercise" in TradingClient)]
+pub struct ExerciseOptionPosition {
+    #[required]
+    #[serde(skip_serializing)]
+    pub symbol_or_contract_id: SymbolOrAssetId,
 }
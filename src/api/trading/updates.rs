@@ -0,0 +1,155 @@
+//! The [trade updates stream](https://docs.alpaca.markets/docs/websocket-streaming), which pushes
+//! the events of the orders of the account (e.g. fills) as they happen.
+//!
+//! ```rust,no_run
+//! # use alpaca_rs::prelude::*;
+//! # use futures::StreamExt;
+//! # async fn run(client: TradingClient) -> Result<(), AlpacaError> {
+//! let mut updates = client.trade_updates().await?;
+//!
+//! while let Some(update) = updates.next().await.transpose()? {
+//!     if update.event == TradeEvent::Fill {
+//!         println!("{} filled at {:?}", update.order.symbol, update.price);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use super::*;
+use async_tungstenite::{
+    async_std::{connect_async, ConnectStream},
+    tungstenite::Message,
+    WebSocketStream,
+};
+use futures::{
+    io::{AsyncRead, AsyncWrite},
+    SinkExt, Stream, StreamExt,
+};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+#[derive(Serialize)]
+#[serde(tag = "action", rename_all = "lowercase")]
+enum Action<'a> {
+    Auth { key: &'a str, secret: &'a str },
+    Listen { data: Streams },
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Streams {
+    streams: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "stream", content = "data", rename_all = "snake_case")]
+enum StreamMessage {
+    Authorization { status: String },
+    Listening(Streams),
+    TradeUpdates(TradeUpdate),
+}
+
+/// A connection to the trade updates stream, created with [`TradingClient::trade_updates`].
+///
+/// The updates are received by using it as a [`Stream`].
+#[must_use = "A stream does not receive anything unless it is polled"]
+pub struct TradeUpdates<S = ConnectStream> {
+    pub socket: WebSocketStream<S>,
+}
+
+impl TradeUpdates {
+    /// Connects to the stream at `url`, authenticates and starts listening to trade updates.
+    pub async fn connect(auth: &TradingAuth, url: Url) -> Result<Self> {
+        let (socket, _) = connect_async(url.as_str()).await?;
+        Self::new(socket, auth).await
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> TradeUpdates<S> {
+    /// Authenticates and starts listening to trade updates on an already connected socket.
+    ///
+    /// This allows using the stream with any async runtime or transport,
+    /// [`TradeUpdates::connect`] connects with `async-std`.
+    pub async fn new(socket: WebSocketStream<S>, auth: &TradingAuth) -> Result<Self> {
+        let mut updates = Self { socket };
+
+        updates
+            .send(&Action::Auth {
+                key: &auth.key_id,
+                secret: &auth.secret,
+            })
+            .await?;
+        match updates.next_message().await? {
+            Some(StreamMessage::Authorization { status }) if status == "authorized" => {}
+            other => {
+                return Err(Error::Stream {
+                    code: None,
+                    message: format!("authorization failed: {other:?}"),
+                })
+            }
+        }
+
+        updates
+            .send(&Action::Listen {
+                data: Streams {
+                    streams: vec!["trade_updates".to_owned()],
+                },
+            })
+            .await?;
+
+        Ok(updates)
+    }
+
+    async fn send(&mut self, action: &Action<'_>) -> Result<()> {
+        self.socket
+            .send(Message::Text(serde_json::to_string(action)?))
+            .await?;
+        Ok(())
+    }
+
+    async fn next_message(&mut self) -> Result<Option<StreamMessage>> {
+        futures::future::poll_fn(|cx| self.poll_message(cx)).await
+    }
+
+    fn poll_message(&mut self, cx: &mut Context<'_>) -> Poll<Result<Option<StreamMessage>>> {
+        loop {
+            let frame = match ready!(self.socket.poll_next_unpin(cx)) {
+                Some(Ok(frame)) => frame,
+                Some(Err(error)) => return Poll::Ready(Err(error.into())),
+                None => return Poll::Ready(Ok(None)),
+            };
+
+            // the paper trading stream sends binary frames, the live one text frames
+            let message = match frame {
+                Message::Text(text) => serde_json::from_str(&text),
+                Message::Binary(binary) => serde_json::from_slice(&binary),
+                Message::Close(_) => return Poll::Ready(Ok(None)),
+                // pings are answered by tungstenite itself
+                _ => continue,
+            };
+
+            return Poll::Ready(message.map(Some).map_err(Into::into));
+        }
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> Stream for TradeUpdates<S> {
+    type Item = Result<TradeUpdate>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            match ready!(this.poll_message(cx)) {
+                Ok(Some(StreamMessage::TradeUpdates(update))) => {
+                    return Poll::Ready(Some(Ok(update)))
+                }
+                Ok(Some(StreamMessage::Listening(Streams { streams }))) => {
+                    trace!("Listening to {streams:?}");
+                }
+                Ok(Some(other)) => trace!("Ignoring trade updates stream message {other:?}"),
+                Ok(None) => return Poll::Ready(None),
+                Err(error) => return Poll::Ready(Some(Err(error))),
+            }
+        }
+    }
+}
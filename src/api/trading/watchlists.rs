@@ -0,0 +1,72 @@
+use super::*;
+
+/// List the watchlists of the account, without their assets.
+#[with_builder(get_watchlists)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, ClientEndpoint)]
+#[endpoint(Get "/watchlists" in TradingClient -> Vec<Watchlist>)]
+pub struct GetWatchlists;
+
+/// Create a watchlist, whose name has to be unique.
+#[with_builder(create_watchlist)]
+#[derive(Serialize, Deserialize, Debug, Clone, ClientEndpoint)]
+#[endpoint(Post(json) "/watchlists" in TradingClient -> Watchlist)]
+pub struct CreateWatchlist {
+    #[required]
+    pub name: String,
+    /// The symbols of the assets in the watchlist.
+    pub symbols: Vec<String>,
+}
+
+/// Get a single watchlist by its ID, with its assets.
+#[with_builder(get_watchlist)]
+#[derive(Serialize, Deserialize, Debug, Clone, ClientEndpoint)]
+#[endpoint(Get(empty) "/watchlists/{watchlist_id}" in TradingClient -> Watchlist)]
+pub struct GetWatchlist {
+    #[required]
+    pub watchlist_id: String,
+}
+
+/// Replace the name and the assets of a watchlist.
+#[with_builder(update_watchlist)]
+#[derive(Serialize, Deserialize, Debug, Clone, ClientEndpoint)]
+#[endpoint(Put(json) "/watchlists/{watchlist_id}" in TradingClient -> Watchlist)]
+pub struct UpdateWatchlist {
+    #[required]
+    #[serde(skip_serializing)]
+    pub watchlist_id: String,
+    #[required]
+    pub name: String,
+    /// The symbols of the assets in the watchlist, replacing the previous ones.
+    pub symbols: Vec<String>,
+}
+
+#[with_builder(delete_watchlist)]
+#[derive(Serialize, Deserialize, Debug, Clone, ClientEndpoint)]
+#[endpoint(Delete(empty, empty) "/watchlists/{watchlist_id}" in TradingClient)]
+pub struct DeleteWatchlist {
+    #[required]
+    pub watchlist_id: String,
+}
+
+/// Add an asset to a watchlist, returning the updated watchlist.
+#[with_builder(add_to_watchlist)]
+#[derive(Serialize, Deserialize, Debug, Clone, ClientEndpoint)]
+#[endpoint(Post(json) "/watchlists/{watchlist_id}" in TradingClient -> Watchlist)]
+pub struct AddToWatchlist {
+    #[required]
+    #[serde(skip_serializing)]
+    pub watchlist_id: String,
+    #[required]
+    pub symbol: String,
+}
+
+/// Remove an asset from a watchlist, returning the updated watchlist.
+#[with_builder(remove_from_watchlist)]
+#[derive(Serialize, Deserialize, Debug, Clone, ClientEndpoint)]
+#[endpoint(Delete(empty) "/watchlists/{watchlist_id}/{symbol}" in TradingClient -> Watchlist)]
+pub struct RemoveFromWatchlist {
+    #[required]
+    pub watchlist_id: String,
+    #[required]
+    pub symbol: String,
+}
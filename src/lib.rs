@@ -36,16 +36,126 @@ pub mod pagination;
 /// This is just an alias to `Result<T, Error>`.
 pub type Result<T, E = Error> = core::result::Result<T, E>;
 
-/// An HTTP error (a "bad request" status code or a network error),
-/// or a serialization error (which means the response from the API was not valid JSON).
+/// An error returned by the Alpaca API (a "bad request" status code), an HTTP error (a network
+/// error), or a serialization error (which means the response from the API was not valid JSON).
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
+    /// The API responded with an unsuccessful status code.
+    #[error("Alpaca API error ({status}): {message}")]
+    Api {
+        /// The HTTP status code of the response.
+        status: http_types::StatusCode,
+        /// The Alpaca specific error code, if the API sent one.
+        code: Option<u32>,
+        /// The error message sent by the API, or the raw response body if it was not a
+        /// structured error.
+        message: String,
+    },
     #[error("{_0}")]
     Http(http_types::Error),
     #[error(transparent)]
     Json(#[from] serde_json::Error),
     #[error(transparent)]
     Query(#[from] acril::serde_urlencoded::ser::Error),
+    /// A real-time data stream sent an error, or a message that was not expected at that point.
+    #[error("stream error: {message}")]
+    Stream {
+        /// The Alpaca specific error code, if the stream sent an error.
+        code: Option<u32>,
+        message: String,
+    },
+    /// A WebSocket error of a real-time data stream.
+    #[cfg(any(feature = "market-data-live", feature = "trading-live"))]
+    #[error(transparent)]
+    WebSocket(Box<async_tungstenite::tungstenite::Error>),
+    /// A real-time data stream sent a message that is not valid MessagePack.
+    #[cfg(feature = "market-data-live")]
+    #[error(transparent)]
+    MessagePackDecode(#[from] rmp_serde::decode::Error),
+    #[cfg(feature = "market-data-live")]
+    #[error(transparent)]
+    MessagePackEncode(#[from] rmp_serde::encode::Error),
+    /// The order would be rejected by Alpaca, so it was not sent.
+    #[error("invalid order: {_0}")]
+    InvalidOrder(&'static str),
+}
+
+impl Error {
+    /// The Alpaca specific error code for an order that exceeds the buying power of the account.
+    pub const INSUFFICIENT_BUYING_POWER: u32 = 40_310_000;
+
+    /// The HTTP status code of the response, if this is an [`Error::Api`].
+    #[must_use]
+    pub fn status(&self) -> Option<http_types::StatusCode> {
+        match self {
+            Self::Api { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// The Alpaca specific error code, if this is an [`Error::Api`] and the API sent one.
+    #[must_use]
+    pub fn code(&self) -> Option<u32> {
+        match self {
+            Self::Api { code, .. } => *code,
+            _ => None,
+        }
+    }
+
+    /// Whether the account does not have enough buying power for an order.
+    #[must_use]
+    pub fn is_insufficient_buying_power(&self) -> bool {
+        self.code() == Some(Self::INSUFFICIENT_BUYING_POWER)
+    }
+
+    /// Whether the requested resource (e.g. an order or a position) does not exist.
+    #[must_use]
+    pub fn is_not_found(&self) -> bool {
+        self.status() == Some(http_types::StatusCode::NotFound)
+    }
+
+    /// Whether the request was rejected because of too many requests.
+    #[must_use]
+    pub fn is_rate_limited(&self) -> bool {
+        self.status() == Some(http_types::StatusCode::TooManyRequests)
+    }
+
+    /// Whether the request was forbidden, e.g. because of invalid credentials, insufficient
+    /// buying power or a blocked account.
+    #[must_use]
+    pub fn is_forbidden(&self) -> bool {
+        self.status() == Some(http_types::StatusCode::Forbidden)
+    }
+
+    /// Turns an unsuccessful response into an [`Error::Api`], passing through successful ones.
+    pub(crate) async fn for_status(mut response: Response) -> Result<Response> {
+        #[derive(serde::Deserialize)]
+        struct ApiErrorBody {
+            code: Option<u32>,
+            message: String,
+        }
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let body = response.body_string().await?;
+        trace!("unsuccessful response: {status}, {body}");
+
+        Err(match serde_json::from_str::<ApiErrorBody>(&body) {
+            Ok(ApiErrorBody { code, message }) => Self::Api {
+                status,
+                code,
+                message,
+            },
+            Err(_) => Self::Api {
+                status,
+                code: None,
+                message: body,
+            },
+        })
+    }
 }
 
 impl From<http_types::Error> for Error {
@@ -54,12 +164,21 @@ impl From<http_types::Error> for Error {
     }
 }
 
+#[cfg(any(feature = "market-data-live", feature = "trading-live"))]
+impl From<async_tungstenite::tungstenite::Error> for Error {
+    fn from(value: async_tungstenite::tungstenite::Error) -> Self {
+        Self::WebSocket(Box::new(value))
+    }
+}
+
 /// `use alpaca_rs::prelude::*;` to import the most commonly used types and clients.
 pub mod prelude {
     pub use crate::model::*;
     pub use crate::{
         api::broker::{BrokerAuth, BrokerClient},
         api::market_data::MarketDataClient,
+        api::rate_limit::RateLimit,
+        api::retry::RetryPolicy,
         api::trading::{TradingAuth, TradingClient},
         Error as AlpacaError,
     };
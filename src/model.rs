@@ -4,9 +4,22 @@ use super::*;
 use std::collections::HashMap;
 use std::fmt::Display;
 
-use chrono::NaiveTime;
+use chrono::{Datelike, NaiveTime, TimeZone};
 use serde::{Deserialize, Serialize};
-use serde_with::{serde_as, skip_serializing_none, DisplayFromStr};
+use serde_with::{serde_as, skip_serializing_none, DefaultOnNull, DisplayFromStr};
+
+/// The number type of prices, money amounts and quantities that Alpaca sends as strings.
+///
+/// This is an [`f64`] by default, with the `decimal` feature enabled it is an exact
+/// [`rust_decimal::Decimal`] instead, which does not lose precision when doing accounting.
+#[cfg(not(feature = "decimal"))]
+pub type Num = f64;
+/// The number type of prices, money amounts and quantities that Alpaca sends as strings.
+///
+/// This is an exact [`rust_decimal::Decimal`] because the `decimal` feature is enabled, without
+/// it this is an [`f64`].
+#[cfg(feature = "decimal")]
+pub type Num = rust_decimal::Decimal;
 
 #[derive(Default, Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -51,6 +64,12 @@ pub enum AccountType {
     DonorAdvised,
 }
 
+/// An account, with its balances, margin and restrictions.
+///
+/// Of the balances, the Broker API only sends `cash`, `portfolio_value`, `last_equity` and
+/// `buying_power`, so the other balances, the margin and the restrictions are [`None`] for its
+/// accounts.
+#[skip_serializing_none]
 #[serde_as]
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct Account {
@@ -60,13 +79,148 @@ pub struct Account {
     pub crypto_status: AccountStatus,
     pub currency: String,
     pub created_at: DateTime,
-    pub last_equity: String,
+    /// The cash balance.
+    #[serde_as(as = "DisplayFromStr")]
+    pub cash: Num,
+    /// The total value of the cash and the positions.
+    #[serde_as(as = "DisplayFromStr")]
+    pub portfolio_value: Num,
+    /// The cash plus the long market value plus the (negative) short market value.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub equity: Option<Num>,
+    /// The equity as of the previous trading day's close.
     #[serde_as(as = "DisplayFromStr")]
-    pub portfolio_value: f64,
+    pub last_equity: Num,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub long_market_value: Option<Num>,
+    /// The market value of the short positions, which is negative.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub short_market_value: Option<Num>,
+    /// The buying power the account can use right now, which depends on the multiplier and on
+    /// whether it is a pattern day trader.
     #[serde_as(as = "DisplayFromStr")]
-    pub cash: f64,
+    pub buying_power: Num,
+    /// The buying power under Regulation T (overnight buying power).
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub regt_buying_power: Option<Num>,
+    /// The buying power for day trades, four times the excess equity of the previous day for
+    /// pattern day traders.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub daytrading_buying_power: Option<Num>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub non_marginable_buying_power: Option<Num>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub options_buying_power: Option<Num>,
+    /// The margin multiplier: `1` for a cash account, `2` for a margin account and `4` for a
+    /// pattern day trader's margin account.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub multiplier: Option<Num>,
+    /// The margin required to open the current positions.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub initial_margin: Option<Num>,
+    /// The margin required to keep the current positions.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub maintenance_margin: Option<Num>,
+    /// The maintenance margin as of the previous trading day's close.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub last_maintenance_margin: Option<Num>,
+    /// The special memorandum account balance as of the previous trading day's close.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub sma: Option<Num>,
+    /// The number of day trades in the last 5 trading days.
+    #[serde(default)]
+    pub daytrade_count: Option<u32>,
+    /// Whether the account is flagged as a pattern day trader.
+    #[serde(default)]
+    pub pattern_day_trader: Option<bool>,
+    /// If true, the account can't place orders.
+    #[serde(default)]
+    pub trading_blocked: Option<bool>,
+    /// If true, the account can't request money transfers.
+    #[serde(default)]
+    pub transfers_blocked: Option<bool>,
+    /// If true, the account activity is restricted.
+    #[serde(default)]
+    pub account_blocked: Option<bool>,
+    /// If true, the user has suspended trading, see [`AccountConfigurations::suspend_trade`].
+    #[serde(default)]
+    pub trade_suspended_by_user: bool,
+    /// Whether the account can short sell.
+    #[serde(default)]
+    pub shorting_enabled: Option<bool>,
+    /// The fees collected, but not yet charged.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub accrued_fees: Option<Num>,
+    /// The cash transferred in, but not yet settled.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub pending_transfer_in: Option<Num>,
+    /// The date of the balances used for the previous day's values, e.g. `last_equity`.
+    pub balance_asof: Option<Date>,
+    /// The approved options trading level.
+    pub options_approved_level: Option<u8>,
+    /// The options trading level in use, see
+    /// [`AccountConfigurations::max_options_trading_level`].
+    pub options_trading_level: Option<u8>,
+}
+
+/// Which orders a buying power or pattern day trader check applies to.
+#[derive(Default, Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum DayTradeCheck {
+    /// Both entering and exiting positions.
+    #[default]
+    Both,
+    /// Only entering positions.
+    Entry,
+    /// Only exiting positions.
+    Exit,
+}
+
+/// Which trades Alpaca sends confirmation emails for.
+#[derive(Default, Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum TradeConfirmEmail {
+    #[default]
+    All,
+    None,
+}
+
+/// The trading settings of an account.
+#[skip_serializing_none]
+#[serde_as]
+#[derive(Default, Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct AccountConfigurations {
+    /// Which orders the day trading buying power check applies to.
+    pub dtbp_check: DayTradeCheck,
+    pub trade_confirm_email: TradeConfirmEmail,
+    /// If true, new orders are blocked.
+    pub suspend_trade: bool,
+    /// If true, the account can't short sell, i.e. is a long-only account.
+    pub no_shorting: bool,
+    /// If true, fractional orders are allowed.
+    pub fractional_trading: bool,
+    /// The maximum margin multiplier, `1` (no margin) or `2` (default for a margin account).
     #[serde_as(as = "DisplayFromStr")]
-    pub buying_power: f64,
+    pub max_margin_multiplier: u8,
+    /// Which orders the pattern day trader check applies to.
+    pub pdt_check: DayTradeCheck,
+    /// The highest options trading level the account uses, at most the approved one: `0`
+    /// (disabled), `1` (covered calls and cash-secured puts) or `2` (buying calls and puts).
+    pub max_options_trading_level: Option<u8>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -218,6 +372,19 @@ impl OrderStatus {
     }
 }
 
+/// The order statuses to filter by when listing orders.
+#[derive(Default, Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum OrderStatusFilter {
+    /// Only orders that have not reached a terminal status.
+    #[default]
+    Open,
+    /// Only orders that have reached a terminal status.
+    Closed,
+    /// Every order, regardless of its status.
+    All,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash, derive_more::Display)]
 #[serde(rename_all = "lowercase")]
 pub enum OrderSide {
@@ -233,39 +400,104 @@ pub enum OrderType {
     Market,
     Limit {
         #[serde_as(as = "DisplayFromStr")]
-        limit_price: f64,
+        limit_price: Num,
     },
     Stop {
         #[serde_as(as = "DisplayFromStr")]
-        stop_price: f64,
+        stop_price: Num,
     },
     StopLimit {
         #[serde_as(as = "DisplayFromStr")]
-        stop_price: f64,
+        stop_price: Num,
         #[serde_as(as = "DisplayFromStr")]
-        limit_price: f64,
+        limit_price: Num,
     },
-    #[serde(untagged)]
     TrailingStop(TrailingStop),
 }
 
+/// The trailing amount of a trailing stop order, either a dollar offset or a percentage of the
+/// high water mark.
 #[serde_as]
 #[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(untagged)]
 pub enum TrailingStop {
-    #[serde(rename = "trail_price")]
-    Price(#[serde_as(as = "DisplayFromStr")] f64),
-    #[serde(rename = "trail_percent")]
-    Percent(#[serde_as(as = "DisplayFromStr")] f64),
+    Price {
+        #[serde_as(as = "DisplayFromStr")]
+        trail_price: Num,
+    },
+    Percent {
+        #[serde_as(as = "DisplayFromStr")]
+        trail_percent: Num,
+    },
 }
 
+/// An order, as returned by the Trading and Broker APIs.
+#[skip_serializing_none]
+#[serde_as]
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Order {
     pub id: String,
+    /// The unique identifier of the order provided by the client, generated by Alpaca if the
+    /// client did not provide one.
+    pub client_order_id: String,
+    pub created_at: DateTime,
+    pub updated_at: Option<DateTime>,
+    pub submitted_at: Option<DateTime>,
+    pub filled_at: Option<DateTime>,
+    pub expired_at: Option<DateTime>,
+    pub canceled_at: Option<DateTime>,
+    pub failed_at: Option<DateTime>,
+    pub replaced_at: Option<DateTime>,
+    /// The ID of the order that replaced this order.
+    pub replaced_by: Option<String>,
+    /// The ID of the order that this order replaced.
+    pub replaces: Option<String>,
+    pub asset_id: String,
     pub symbol: String,
-    pub status: OrderStatus,
-    pub side: OrderSide,
+    pub asset_class: AssetClass,
+    /// The dollar amount to trade, if the order was placed with a notional amount.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub notional: Option<Num>,
+    /// The number of shares to trade, if the order was placed with a quantity.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub qty: Option<Num>,
+    #[serde_as(as = "DisplayFromStr")]
+    pub filled_qty: Num,
+    /// The average price of the filled shares, [`None`] if nothing has been filled yet.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub filled_avg_price: Option<Num>,
+    pub order_class: OrderClass,
     #[serde(rename = "type", flatten)]
     pub kind: OrderType,
+    pub side: OrderSide,
+    pub time_in_force: OrderTif,
+    pub status: OrderStatus,
+    pub extended_hours: bool,
+    /// The legs of a multi-leg order (e.g. a bracket order), only present if the order was
+    /// requested with `nested`.
+    #[serde_as(as = "DefaultOnNull")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub legs: Vec<Order>,
+    /// The ratio of the quantity of this leg to the quantity of its multi-leg options order.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub ratio_qty: Option<Num>,
+    /// The highest (or lowest, for buy orders) price since the submission of a trailing stop
+    /// order.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub hwm: Option<Num>,
+}
+
+/// The result of cancelling a single order as a part of cancelling all open orders.
+#[skip_serializing_none]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderCancellation {
+    /// The ID of the order.
+    pub id: String,
+    /// The HTTP status code of the cancellation of this order.
+    pub status: u16,
+    /// The cancelled order, if it was cancelled successfully.
+    pub body: Option<Order>,
 }
 
 #[derive(
@@ -311,6 +543,7 @@ pub enum OrderClass {
     /// Any non-bracket order (i.e., regular market, limit, or stop loss
     /// orders).
     #[default]
+    #[serde(alias = "")]
     Simple,
     /// A bracket order is a chain of three orders that can be used to manage your
     /// position entry and exit. It is a common use case of an
@@ -329,6 +562,56 @@ pub enum OrderClass {
     #[serde(rename = "oto")]
     #[display(fmt = "one triggers other")]
     OneTriggersOther,
+    /// A multi-leg options order, e.g. a spread, whose legs are filled together.
+    #[serde(rename = "mleg")]
+    #[display(fmt = "multi-leg")]
+    MultiLeg,
+}
+
+/// Whether an options order opens or closes a position.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum PositionIntent {
+    BuyToOpen,
+    BuyToClose,
+    SellToOpen,
+    SellToClose,
+}
+
+/// A leg of a multi-leg options order.
+#[skip_serializing_none]
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct OrderLeg {
+    /// The symbol of the option contract, see [`OptionSymbol`].
+    pub symbol: String,
+    /// The quantity of this leg per unit of the quantity of the whole order.
+    #[serde_as(as = "DisplayFromStr")]
+    pub ratio_qty: u32,
+    pub side: OrderSide,
+    pub position_intent: Option<PositionIntent>,
+}
+
+/// The take-profit leg of a bracket, OCO or OTO order.
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub struct TakeProfit {
+    /// The limit price of the take-profit order.
+    #[serde_as(as = "DisplayFromStr")]
+    pub limit_price: Num,
+}
+
+/// The stop-loss leg of a bracket, OCO or OTO order.
+#[skip_serializing_none]
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub struct StopLoss {
+    /// The stop price of the stop-loss order.
+    #[serde_as(as = "DisplayFromStr")]
+    pub stop_price: Num,
+    /// If set, the stop-loss order becomes a stop-limit order with this limit price.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub limit_price: Option<Num>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Hash)]
@@ -448,7 +731,7 @@ pub struct Transfer {
     pub status: TransferStatus,
     pub reason: Option<String>,
     #[serde_as(as = "DisplayFromStr")]
-    pub amount: f64,
+    pub amount: Num,
     pub direction: Direction,
     pub created_at: DateTime,
     pub updated_at: Option<DateTime>,
@@ -473,11 +756,517 @@ pub enum Side {
     Short,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum AssetClass {
+    #[default]
     UsEquity,
     Crypto,
+    /// Option contracts on US equities.
+    UsOption,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum AssetStatus {
+    Active,
+    Inactive,
+}
+
+/// A stock or crypto asset that can be traded (or could be, see [`Asset::tradable`]) on Alpaca.
+#[skip_serializing_none]
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Asset {
+    pub id: String,
+    pub class: AssetClass,
+    /// The exchange the asset is listed on, e.g. `NASDAQ`, or `CRYPTO` for crypto assets.
+    pub exchange: String,
+    pub symbol: String,
+    pub name: String,
+    pub status: AssetStatus,
+    /// Whether the asset can be traded on Alpaca.
+    pub tradable: bool,
+    /// Whether the asset can be bought on margin.
+    pub marginable: bool,
+    /// Whether the asset can be sold short.
+    pub shortable: bool,
+    /// Whether the asset is easy to borrow, i.e. whether it can be sold short without any fees.
+    pub easy_to_borrow: bool,
+    /// Whether fractional quantities of the asset can be traded.
+    pub fractionable: bool,
+    /// The margin requirement of a long position, in percent.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub margin_requirement_long: Option<Num>,
+    /// The margin requirement of a short position, in percent.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub margin_requirement_short: Option<Num>,
+    /// Additional attributes of the asset, e.g. `has_options` or `ipo`.
+    #[serde_as(as = "DefaultOnNull")]
+    #[serde(default)]
+    pub attributes: Vec<String>,
+    /// The minimum quantity of an order, only for crypto assets.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub min_order_size: Option<Num>,
+    /// The smallest step of an order quantity, only for crypto assets.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub min_trade_increment: Option<Num>,
+    /// The smallest step of a price, only for crypto assets.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub price_increment: Option<Num>,
+}
+
+/// A named list of assets.
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Watchlist {
+    pub id: String,
+    pub account_id: String,
+    pub name: String,
+    pub created_at: DateTime,
+    pub updated_at: DateTime,
+    /// The assets in the watchlist, empty when listing the watchlists.
+    #[serde_as(as = "DefaultOnNull")]
+    #[serde(default)]
+    pub assets: Vec<Asset>,
+}
+
+/// Defines [`ActivityType`] from the code of each activity type in the API.
+macro_rules! activity_types {
+    ($($(#[doc = $doc:literal])* $variant:ident => $code:literal,)*) => {
+        /// The type of an account [`Activity`], which is sent as a code like `FILL` or `DIV`.
+        ///
+        /// Types that are not known yet are kept as [`ActivityType::Other`] with their code.
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum ActivityType {
+            $($(#[doc = $doc])* $variant,)*
+            Other(String),
+        }
+
+        impl ActivityType {
+            /// The code of the activity type in the API, e.g. `FILL`.
+            #[must_use]
+            pub fn code(&self) -> &str {
+                match self {
+                    $(Self::$variant => $code,)*
+                    Self::Other(code) => code,
+                }
+            }
+        }
+
+        impl std::str::FromStr for ActivityType {
+            type Err = std::convert::Infallible;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Ok(match s {
+                    $($code => Self::$variant,)*
+                    other => Self::Other(other.to_owned()),
+                })
+            }
+        }
+    };
+}
+
+activity_types! {
+    /// An order fill, either partial or full.
+    Fill => "FILL",
+    /// A cash transfer, either a deposit or a withdrawal.
+    Transfer => "TRANS",
+    /// A miscellaneous or rarely used activity.
+    Misc => "MISC",
+    /// An ACATS in/out transfer of cash.
+    AcatsCash => "ACATC",
+    /// An ACATS in/out transfer of securities.
+    AcatsSecurities => "ACATS",
+    /// A crypto fee.
+    CryptoFee => "CFEE",
+    /// A cash deposit.
+    CashDeposit => "CSD",
+    /// A cash withdrawal.
+    CashWithdrawal => "CSW",
+    /// A dividend.
+    Dividend => "DIV",
+    /// A long term capital gain dividend.
+    DividendCapitalGainLong => "DIVCGL",
+    /// A short term capital gain dividend.
+    DividendCapitalGainShort => "DIVCGS",
+    /// A fee on a dividend.
+    DividendFee => "DIVFEE",
+    /// A dividend adjusted for foreign tax withheld.
+    DividendForeignTax => "DIVFT",
+    /// A dividend adjusted for tax withheld from non-resident aliens.
+    DividendNra => "DIVNRA",
+    /// A return of capital dividend.
+    DividendReturnOfCapital => "DIVROC",
+    /// A dividend adjusted for tefra withholding.
+    DividendTefraWithholding => "DIVTW",
+    /// A tax exempt dividend.
+    DividendTaxExempt => "DIVTXEX",
+    /// A regulatory or pass-through fee.
+    Fee => "FEE",
+    /// Interest, credited or charged.
+    Interest => "INT",
+    /// Interest adjusted for tax withheld from non-resident aliens.
+    InterestNra => "INTNRA",
+    /// Interest adjusted for tefra withholding.
+    InterestTefraWithholding => "INTTW",
+    /// A journal entry.
+    Journal => "JNL",
+    /// A journal entry of cash.
+    JournalCash => "JNLC",
+    /// A journal entry of stock.
+    JournalStock => "JNLS",
+    /// A merger or acquisition.
+    Acquisition => "MA",
+    /// A name change.
+    NameChange => "NC",
+    /// An option assignment.
+    OptionAssignment => "OPASN",
+    /// An option expiration.
+    OptionExpiration => "OPEXP",
+    /// An option exercise.
+    OptionExercise => "OPXRC",
+    /// A pass-through charge.
+    PassThroughCharge => "PTC",
+    /// A pass-through rebate.
+    PassThroughRebate => "PTR",
+    /// A reorganization.
+    Reorganization => "REORG",
+    /// A symbol change.
+    SymbolChange => "SC",
+    /// A stock spinoff.
+    StockSpinoff => "SSO",
+    /// A stock split.
+    StockSplit => "SSP",
+    /// A stock split, as sent for some accounts.
+    Split => "SPLIT",
+}
+
+impl Serialize for ActivityType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for ActivityType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        DisplayFromStr::deserialize_as(deserializer)
+    }
+}
+
+impl Display for ActivityType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum FillType {
+    Fill,
+    PartialFill,
+}
+
+/// The side of a fill, which unlike [`OrderSide`] tells short sales apart.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum FillSide {
+    Buy,
+    Sell,
+    SellShort,
+}
+
+/// An order fill in the account activities.
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct TradeActivity {
+    pub id: String,
+    pub activity_type: ActivityType,
+    pub transaction_time: DateTime,
+    #[serde(rename = "type")]
+    pub fill_type: FillType,
+    #[serde_as(as = "DisplayFromStr")]
+    pub price: Num,
+    /// The quantity of this fill.
+    #[serde_as(as = "DisplayFromStr")]
+    pub qty: Num,
+    pub side: FillSide,
+    pub symbol: String,
+    /// The quantity of the order that is left to fill.
+    #[serde_as(as = "DisplayFromStr")]
+    pub leaves_qty: Num,
+    pub order_id: String,
+    /// The quantity of the order that has been filled so far.
+    #[serde_as(as = "DisplayFromStr")]
+    pub cum_qty: Num,
+    pub order_status: OrderStatus,
+}
+
+/// Any account activity other than a fill, e.g. a dividend or a transfer.
+#[skip_serializing_none]
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct NonTradeActivity {
+    pub id: String,
+    pub activity_type: ActivityType,
+    /// A more specific type of the activity, if any.
+    pub activity_sub_type: Option<String>,
+    pub date: Date,
+    /// The amount of cash credited to (positive) or debited from (negative) the account.
+    #[serde_as(as = "DisplayFromStr")]
+    pub net_amount: Num,
+    pub description: Option<String>,
+    /// Whether the activity has been executed, corrected or canceled.
+    pub status: Option<String>,
+    /// The symbol of the security the activity is about, e.g. the one paying a dividend.
+    pub symbol: Option<String>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub qty: Option<Num>,
+    /// The amount per share, e.g. of a dividend.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub per_share_amount: Option<Num>,
+}
+
+/// An activity of the account, grouped by its [`ActivityType`]: fills, or cash and position
+/// changes like dividends and transfers.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(untagged, from = "RawActivity")]
+pub enum Activity {
+    /// An order fill.
+    Fill(TradeActivity),
+    /// Any kind of dividend, including the fees and taxes on it.
+    Dividend(NonTradeActivity),
+    /// Interest, including the taxes on it.
+    Interest(NonTradeActivity),
+    /// A fee, including crypto and pass-through fees.
+    Fee(NonTradeActivity),
+    /// A journal entry of cash or stock.
+    Journal(NonTradeActivity),
+    /// A transfer of cash or securities into or out of the account.
+    Transfer(NonTradeActivity),
+    /// A stock split.
+    Split(NonTradeActivity),
+    /// An option assignment, exercise or expiration.
+    Option(NonTradeActivity),
+    /// Any other activity, e.g. a merger or a symbol change.
+    Other(NonTradeActivity),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawActivity {
+    Trade(TradeActivity),
+    NonTrade(NonTradeActivity),
+}
+
+impl From<RawActivity> for Activity {
+    fn from(raw: RawActivity) -> Self {
+        use ActivityType::*;
+
+        let activity = match raw {
+            RawActivity::Trade(activity) => return Self::Fill(activity),
+            RawActivity::NonTrade(activity) => activity,
+        };
+
+        match activity.activity_type {
+            Dividend
+            | DividendCapitalGainLong
+            | DividendCapitalGainShort
+            | DividendFee
+            | DividendForeignTax
+            | DividendNra
+            | DividendReturnOfCapital
+            | DividendTefraWithholding
+            | DividendTaxExempt => Self::Dividend(activity),
+            Interest | InterestNra | InterestTefraWithholding => Self::Interest(activity),
+            Fee | CryptoFee | PassThroughCharge | PassThroughRebate => Self::Fee(activity),
+            Journal | JournalCash | JournalStock => Self::Journal(activity),
+            Transfer | CashDeposit | CashWithdrawal | AcatsCash | AcatsSecurities => {
+                Self::Transfer(activity)
+            }
+            StockSplit | Split => Self::Split(activity),
+            OptionAssignment | OptionExercise | OptionExpiration => Self::Option(activity),
+            _ => Self::Other(activity),
+        }
+    }
+}
+
+impl Activity {
+    #[must_use]
+    pub fn activity_type(&self) -> &ActivityType {
+        match self {
+            Self::Fill(fill) => &fill.activity_type,
+            Self::Dividend(activity)
+            | Self::Interest(activity)
+            | Self::Fee(activity)
+            | Self::Journal(activity)
+            | Self::Transfer(activity)
+            | Self::Split(activity)
+            | Self::Option(activity)
+            | Self::Other(activity) => &activity.activity_type,
+        }
+    }
+}
+
+impl Identifiable for Activity {
+    fn id(&self) -> String {
+        match self {
+            Self::Fill(fill) => fill.id.clone(),
+            Self::Dividend(activity)
+            | Self::Interest(activity)
+            | Self::Fee(activity)
+            | Self::Journal(activity)
+            | Self::Transfer(activity)
+            | Self::Split(activity)
+            | Self::Option(activity)
+            | Self::Other(activity) => activity.id.clone(),
+        }
+    }
+}
+
+/// The resolution of a [`PortfolioHistory`].
+#[derive(Default, Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PortfolioTimeframe {
+    #[serde(rename = "1Min")]
+    Minute,
+    #[serde(rename = "5Min")]
+    FiveMinutes,
+    #[serde(rename = "15Min")]
+    FifteenMinutes,
+    #[serde(rename = "1H")]
+    Hour,
+    #[default]
+    #[serde(rename = "1D")]
+    Day,
+}
+
+/// Which hours an intraday [`PortfolioHistory`] covers.
+#[derive(Default, Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum IntradayReporting {
+    /// Only the regular trading hours, 9:30 to 16:00 ET.
+    #[default]
+    MarketHours,
+    /// The regular and the extended trading hours, 4:00 to 20:00 ET.
+    ExtendedHours,
+    /// All day, for crypto.
+    Continuous,
+}
+
+/// When the profit/loss of a [`PortfolioHistory`] resets to zero.
+#[derive(Default, Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum PnlReset {
+    /// The profit/loss is relative to the previous trading day's close.
+    #[default]
+    PerDay,
+    /// The profit/loss is relative to the start of the history.
+    NoReset,
+}
+
+/// The equity and profit/loss of the account over time, as parallel series.
+///
+/// Use [`PortfolioHistory::points`] to iterate over them together.
+#[skip_serializing_none]
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct PortfolioHistory {
+    #[serde_as(as = "Vec<serde_with::TimestampSeconds<i64>>")]
+    pub timestamp: Vec<DateTime>,
+    /// The equity of the account at each timestamp, [`None`] where it is not known (e.g. before
+    /// the account was funded).
+    pub equity: Vec<Option<f64>>,
+    pub profit_loss: Vec<Option<f64>>,
+    /// The profit/loss as a fraction of the base value, e.g. `0.01` for 1%.
+    pub profit_loss_pct: Vec<Option<f64>>,
+    /// The equity the profit/loss is relative to.
+    pub base_value: Option<f64>,
+    /// The date of the base value.
+    pub base_value_asof: Option<Date>,
+    pub timeframe: PortfolioTimeframe,
+}
+
+/// A single point of a [`PortfolioHistory`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PortfolioPoint {
+    pub timestamp: DateTime,
+    pub equity: f64,
+    pub profit_loss: f64,
+    pub profit_loss_pct: f64,
+}
+
+impl PortfolioHistory {
+    /// The points of the history in chronological order, skipping the ones with missing values.
+    pub fn points(&self) -> impl Iterator<Item = PortfolioPoint> + '_ {
+        self.timestamp
+            .iter()
+            .zip(&self.equity)
+            .zip(&self.profit_loss)
+            .zip(&self.profit_loss_pct)
+            .filter_map(|(((timestamp, equity), profit_loss), profit_loss_pct)| {
+                Some(PortfolioPoint {
+                    timestamp: *timestamp,
+                    equity: (*equity)?,
+                    profit_loss: (*profit_loss)?,
+                    profit_loss_pct: (*profit_loss_pct)?,
+                })
+            })
+    }
+
+    /// The largest decline of the equity from a previous peak, as a fraction of the peak, e.g.
+    /// `0.2` for a 20% drawdown. This is `0.0` if the equity never declined.
+    #[must_use]
+    pub fn max_drawdown(&self) -> f64 {
+        let mut peak = f64::NEG_INFINITY;
+        let mut max_drawdown = 0.0_f64;
+
+        for point in self.points() {
+            peak = peak.max(point.equity);
+            if peak > 0.0 {
+                max_drawdown = max_drawdown.max((peak - point.equity) / peak);
+            }
+        }
+
+        max_drawdown
+    }
+
+    /// The return of each day relative to the previous day, as a fraction of the previous day's
+    /// equity, e.g. `-0.01` for a 1% loss. The equity of a day is its last point, so this works
+    /// with intraday histories too, and the first day has no return.
+    ///
+    /// Days are dates in New York time, which is what the daily points are stamped with (their
+    /// midnight), and which keeps the evening points of a [`IntradayReporting::Continuous`]
+    /// history on their own trading day.
+    ///
+    /// A day after a day without equity (`0.0`) has no return, as it would be infinite.
+    #[must_use]
+    pub fn daily_returns(&self) -> Vec<(Date, f64)> {
+        let mut closes: Vec<(Date, f64)> = Vec::new();
+        for point in self.points() {
+            let date = point
+                .timestamp
+                .with_timezone(&chrono_tz::America::New_York)
+                .date_naive();
+            match closes.last_mut() {
+                Some((last_date, equity)) if *last_date == date => *equity = point.equity,
+                _ => closes.push((date, point.equity)),
+            }
+        }
+
+        closes
+            .windows(2)
+            .filter(|days| days[0].1 != 0.0)
+            .map(|days| (days[1].0, days[1].1 / days[0].1 - 1.0))
+            .collect()
+    }
 }
 
 #[skip_serializing_none]
@@ -487,31 +1276,31 @@ pub struct OpenPosition {
     pub asset_id: String,
     pub symbol: String,
     pub exchange: String,
-    pub asset_class: String,
+    pub asset_class: AssetClass,
     pub asset_marginable: Option<bool>,
     #[serde_as(as = "DisplayFromStr")]
-    pub avg_entry_price: f64,
+    pub avg_entry_price: Num,
     #[serde_as(as = "DisplayFromStr")]
-    pub qty: i64,
+    pub qty: Num,
     pub side: Side,
     #[serde_as(as = "DisplayFromStr")]
-    pub market_value: f64,
+    pub market_value: Num,
     #[serde_as(as = "DisplayFromStr")]
-    pub cost_basis: f64,
+    pub cost_basis: Num,
     #[serde_as(as = "DisplayFromStr")]
-    pub unrealized_pl: f64,
+    pub unrealized_pl: Num,
     #[serde_as(as = "DisplayFromStr")]
-    pub unrealized_plpc: f64,
+    pub unrealized_plpc: Num,
     #[serde_as(as = "DisplayFromStr")]
-    pub unrealized_intraday_pl: f64,
+    pub unrealized_intraday_pl: Num,
     #[serde_as(as = "DisplayFromStr")]
-    pub unrealized_intraday_plpc: f64,
+    pub unrealized_intraday_plpc: Num,
     #[serde_as(as = "DisplayFromStr")]
-    pub current_price: f64,
+    pub current_price: Num,
     #[serde_as(as = "DisplayFromStr")]
-    pub lastday_price: f64,
+    pub lastday_price: Num,
     #[serde_as(as = "DisplayFromStr")]
-    pub change_today: f64,
+    pub change_today: Num,
     pub swap_rate: Option<String>,
 }
 
@@ -537,19 +1326,20 @@ impl Display for SymbolOrAssetId {
 pub enum OrderAmount {
     #[serde(rename = "qty")]
     #[display(fmt = "{_0} shares of")]
-    /// Number of shares.
-    Quantity(#[serde_as(as = "DisplayFromStr")] i64),
+    /// Number of shares, which can be fractional.
+    Quantity(#[serde_as(as = "DisplayFromStr")] Num),
     #[serde(rename = "notional")]
     #[display(fmt = "${_0} worth of")]
     /// Notional amount is the amount of stock in the currency of the account.
-    Notional(#[serde_as(as = "DisplayFromStr")] f64),
+    Notional(#[serde_as(as = "DisplayFromStr")] Num),
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
 pub struct HistoricalAuctions {
     pub next_page_token: Option<String>,
     pub currency: Option<String>,
-    pub auctions: Vec<HistoricalAuction>,
+    /// The auctions of each symbol.
+    pub auctions: HashMap<String, Vec<HistoricalAuction>>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
@@ -561,7 +1351,7 @@ pub struct SingleAuction {
     #[serde(rename = "p")]
     pub price: f64,
     #[serde(rename = "s")]
-    pub size: Option<i64>,
+    pub size: Option<f64>,
     #[serde(rename = "c")]
     pub condition: String,
 }
@@ -669,9 +1459,11 @@ pub struct HistoricalBars {
     pub currency: Option<String>,
 }
 
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct HistoricalBar {
     #[serde(rename = "t")]
+    #[serde_as(as = "StreamTimestamp")]
     pub timestamp: DateTime,
     #[serde(rename = "o")]
     pub opening_price: f64,
@@ -682,7 +1474,7 @@ pub struct HistoricalBar {
     #[serde(rename = "c")]
     pub closing_price: f64,
     #[serde(rename = "v")]
-    pub volume: i64,
+    pub volume: f64,
     #[serde(rename = "n")]
     pub trade_count: i64,
     #[serde(rename = "vw")]
@@ -729,22 +1521,24 @@ pub enum QuoteZone {
     Otc,
 }
 
+#[serde_as]
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct Quote {
     #[serde(rename = "t")]
+    #[serde_as(as = "StreamTimestamp")]
     pub timestamp: DateTime,
     #[serde(rename = "bx")]
     pub bid_exchange: String,
     #[serde(rename = "bp")]
     pub bid_price: f64,
     #[serde(rename = "bs")]
-    pub bid_size: u32,
+    pub bid_size: f64,
     #[serde(rename = "ax")]
     pub ask_exchange: String,
     #[serde(rename = "ap")]
     pub ask_price: f64,
     #[serde(rename = "as")]
-    pub ask_size: u32,
+    pub ask_size: f64,
     #[serde(rename = "c")]
     pub condition_flags: Vec<String>,
     // TODO(doc): is the name `zone` correct for `z`?
@@ -754,7 +1548,8 @@ pub struct Quote {
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct HistoricalQuotes {
-    pub quotes: Vec<Quote>,
+    /// The quotes of each symbol.
+    pub quotes: HashMap<String, Vec<Quote>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub currency: Option<String>,
     pub next_page_token: Option<String>,
@@ -799,16 +1594,18 @@ pub struct CalendarDay {
     pub settlement_date: Date,
 }
 
+#[serde_as]
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct HistoricalTrade {
     #[serde(rename = "t")]
+    #[serde_as(as = "StreamTimestamp")]
     pub timestamp: DateTime,
     #[serde(rename = "x")]
     pub exchange_code: String,
     #[serde(rename = "p")]
     pub price: f64,
     #[serde(rename = "s")]
-    pub size: u32,
+    pub size: f64,
     #[serde(rename = "i")]
     pub trade_id: i64,
     #[serde(rename = "c")]
@@ -830,7 +1627,8 @@ pub struct Snapshot {
 
 #[derive(Default, Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct HistoricalTrades {
-    pub trades: Vec<HistoricalTrade>,
+    /// The trades of each symbol.
+    pub trades: HashMap<String, Vec<HistoricalTrade>>,
     pub next_page_token: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub currency: Option<String>,
@@ -842,3 +1640,849 @@ pub struct LatestTrades {
     pub trades: Vec<HistoricalTrade>,
     pub currency: Option<String>,
 }
+
+/// The side of the taker of a crypto trade.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TakerSide {
+    #[serde(rename = "B")]
+    Buy,
+    #[serde(rename = "S")]
+    Sell,
+    /// The side is not known, e.g. for trades that happened before Alpaca recorded it.
+    #[serde(other, rename = "-")]
+    Unknown,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct CryptoTrade {
+    #[serde(rename = "t")]
+    #[serde_as(as = "StreamTimestamp")]
+    pub timestamp: DateTime,
+    #[serde(rename = "p")]
+    pub price: f64,
+    /// The size of the trade, which can be fractional.
+    #[serde(rename = "s")]
+    pub size: f64,
+    #[serde(rename = "i")]
+    pub trade_id: i64,
+    #[serde(rename = "tks")]
+    pub taker_side: TakerSide,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct CryptoQuote {
+    #[serde(rename = "t")]
+    #[serde_as(as = "StreamTimestamp")]
+    pub timestamp: DateTime,
+    #[serde(rename = "bp")]
+    pub bid_price: f64,
+    #[serde(rename = "bs")]
+    pub bid_size: f64,
+    #[serde(rename = "ap")]
+    pub ask_price: f64,
+    #[serde(rename = "as")]
+    pub ask_size: f64,
+}
+
+/// A price level of a [`CryptoOrderbook`].
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub struct OrderbookEntry {
+    #[serde(rename = "p")]
+    pub price: f64,
+    #[serde(rename = "s")]
+    pub size: f64,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct CryptoOrderbook {
+    #[serde(rename = "t")]
+    #[serde_as(as = "StreamTimestamp")]
+    pub timestamp: DateTime,
+    #[serde(rename = "b")]
+    pub bids: Vec<OrderbookEntry>,
+    #[serde(rename = "a")]
+    pub asks: Vec<OrderbookEntry>,
+}
+
+#[derive(Default, Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct CryptoHistoricalTrades {
+    /// The trades of each symbol.
+    pub trades: HashMap<String, Vec<CryptoTrade>>,
+    pub next_page_token: Option<String>,
+}
+
+#[derive(Default, Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct CryptoHistoricalQuotes {
+    /// The quotes of each symbol.
+    pub quotes: HashMap<String, Vec<CryptoQuote>>,
+    pub next_page_token: Option<String>,
+}
+
+#[derive(Default, Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct CryptoLatestBars {
+    pub bars: HashMap<String, HistoricalBar>,
+}
+
+#[derive(Default, Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct CryptoLatestTrades {
+    pub trades: HashMap<String, CryptoTrade>,
+}
+
+#[derive(Default, Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct CryptoLatestQuotes {
+    pub quotes: HashMap<String, CryptoQuote>,
+}
+
+#[derive(Default, Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct CryptoLatestOrderbooks {
+    pub orderbooks: HashMap<String, CryptoOrderbook>,
+}
+
+/// The latest market data of a crypto symbol, any of which is missing if there is no data yet.
+#[skip_serializing_none]
+#[derive(Default, Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CryptoSnapshot {
+    pub daily_bar: Option<HistoricalBar>,
+    pub latest_quote: Option<CryptoQuote>,
+    pub latest_trade: Option<CryptoTrade>,
+    pub minute_bar: Option<HistoricalBar>,
+    #[serde(rename = "prevDailyBar")]
+    pub previous_daily_bar: Option<HistoricalBar>,
+}
+
+#[derive(Default, Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct CryptoSnapshots {
+    pub snapshots: HashMap<String, CryptoSnapshot>,
+}
+
+/// Whether an option contract is a call or a put.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum OptionType {
+    Call,
+    Put,
+}
+
+/// The OCC symbol of an option contract, e.g. `AAPL240119C00150000` for the AAPL call with a
+/// strike price of $150 that expires on 2024-01-19.
+///
+/// It can be used wherever a symbol is expected, by converting it into a [`String`]:
+///
+/// ```rust
+/// # use alpaca_rs::prelude::*;
+/// let symbol: OptionSymbol = "AAPL240119C00150000".parse().unwrap();
+/// assert_eq!(symbol.underlying(), "AAPL");
+/// assert_eq!(symbol.kind(), OptionType::Call);
+/// assert_eq!(String::from(symbol), "AAPL240119C00150000");
+/// ```
+///
+/// The fields are only set through [`OptionSymbol::new`] or parsing, which check that they fit
+/// into the symbol, so that it always parses back.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionSymbol {
+    underlying: String,
+    expiration: Date,
+    kind: OptionType,
+    /// The strike price in thousandths of a dollar, as encoded in the symbol.
+    strike: u32,
+}
+
+impl OptionSymbol {
+    /// Fails if the underlying is empty or contains spaces, if the expiration is not between 2000
+    /// and 2068 (the symbol only has the last two digits of the year), or if the strike price is
+    /// negative, has more than three decimals, or exceeds $99,999.999.
+    pub fn new(
+        underlying: impl Into<String>,
+        expiration: Date,
+        kind: OptionType,
+        strike: Num,
+    ) -> Result<Self, &'static str> {
+        let underlying = underlying.into();
+        if underlying.is_empty() {
+            return Err("no underlying symbol");
+        }
+        if !underlying.is_ascii() || underlying.contains(' ') {
+            return Err("invalid underlying symbol");
+        }
+        if !(2000..=2068).contains(&expiration.year()) {
+            return Err("invalid expiration date");
+        }
+        let strike = Self::thousandths(strike).ok_or("invalid strike price")?;
+
+        Ok(Self {
+            underlying,
+            expiration,
+            kind,
+            strike,
+        })
+    }
+
+    /// The root symbol, usually the symbol of the underlying.
+    pub fn underlying(&self) -> &str {
+        &self.underlying
+    }
+
+    pub fn expiration(&self) -> Date {
+        self.expiration
+    }
+
+    pub fn kind(&self) -> OptionType {
+        self.kind
+    }
+
+    /// The strike price, with at most three decimals.
+    pub fn strike(&self) -> Num {
+        #[cfg(not(feature = "decimal"))]
+        {
+            f64::from(self.strike) / 1000.0
+        }
+        #[cfg(feature = "decimal")]
+        {
+            Num::new(self.strike.into(), 3).normalize()
+        }
+    }
+
+    /// The strike price in thousandths of a dollar, as encoded in the symbol, or [`None`] if it
+    /// does not fit.
+    fn thousandths(strike: Num) -> Option<u32> {
+        // eight digits
+        const MAX: u32 = 99_999_999;
+
+        #[cfg(not(feature = "decimal"))]
+        {
+            let thousandths = strike * 1000.0;
+            // allow for the error of e.g. 0.001 * 1000.0
+            let whole = (thousandths - thousandths.round()).abs() < 1e-6;
+            (whole && (0.0..=f64::from(MAX)).contains(&thousandths))
+                .then(|| thousandths.round() as u32)
+        }
+        #[cfg(feature = "decimal")]
+        {
+            use rust_decimal::prelude::ToPrimitive;
+
+            let thousandths = strike * Num::from(1000);
+            if !thousandths.fract().is_zero() {
+                return None;
+            }
+            thousandths
+                .to_u32()
+                .filter(|&thousandths| thousandths <= MAX)
+        }
+    }
+}
+
+impl Serialize for OptionSymbol {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        DisplayFromStr::serialize_as(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for OptionSymbol {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        DisplayFromStr::deserialize_as(deserializer)
+    }
+}
+
+impl Display for OptionSymbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self.kind {
+            OptionType::Call => 'C',
+            OptionType::Put => 'P',
+        };
+        write!(
+            f,
+            "{}{}{kind}{:08}",
+            self.underlying,
+            self.expiration.format("%y%m%d"),
+            self.strike
+        )
+    }
+}
+
+impl std::str::FromStr for OptionSymbol {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // the expiration, the kind and the strike have a fixed length, the root does not
+        if !s.is_ascii() || s.len() < 16 {
+            return Err("too short");
+        }
+        let (underlying, rest) = s.split_at(s.len() - 15);
+        let (expiration, rest) = rest.split_at(6);
+        let (kind, strike) = rest.split_at(1);
+
+        // the root is padded with spaces to six characters in the standard format
+        let underlying = underlying.trim_end();
+        if underlying.is_empty() {
+            return Err("no underlying symbol");
+        }
+        if underlying.contains(' ') {
+            return Err("invalid underlying symbol");
+        }
+        let expiration =
+            Date::parse_from_str(expiration, "%y%m%d").map_err(|_| "invalid expiration date")?;
+        let kind = match kind {
+            "C" => OptionType::Call,
+            "P" => OptionType::Put,
+            _ => return Err("invalid option type"),
+        };
+        if !strike.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err("invalid strike price");
+        }
+        let strike: u32 = strike.parse().map_err(|_| "invalid strike price")?;
+
+        Ok(Self {
+            underlying: underlying.to_owned(),
+            expiration,
+            kind,
+            strike,
+        })
+    }
+}
+
+impl From<OptionSymbol> for String {
+    fn from(symbol: OptionSymbol) -> Self {
+        symbol.to_string()
+    }
+}
+
+impl From<OptionSymbol> for SymbolOrAssetId {
+    fn from(symbol: OptionSymbol) -> Self {
+        Self::SymbolId(symbol.to_string())
+    }
+}
+
+/// The exercise style of an option contract.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum OptionStyle {
+    /// Can be exercised at any time before the expiration.
+    American,
+    /// Can only be exercised at the expiration.
+    European,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum OptionContractStatus {
+    Active,
+    Inactive,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum DeliverableType {
+    Cash,
+    Equity,
+}
+
+/// What is delivered when an option contract is exercised.
+#[skip_serializing_none]
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct OptionDeliverable {
+    #[serde(rename = "type")]
+    pub kind: DeliverableType,
+    pub symbol: String,
+    pub asset_id: Option<String>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub amount: Option<Num>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub allocation_percentage: Option<Num>,
+    pub settlement_type: String,
+    pub settlement_method: String,
+    pub delayed_settlement: bool,
+}
+
+#[skip_serializing_none]
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct OptionContract {
+    pub id: String,
+    pub symbol: OptionSymbol,
+    pub name: String,
+    pub status: OptionContractStatus,
+    pub tradable: bool,
+    pub expiration_date: Date,
+    pub root_symbol: String,
+    pub underlying_symbol: String,
+    pub underlying_asset_id: String,
+    #[serde(rename = "type")]
+    pub kind: OptionType,
+    pub style: OptionStyle,
+    #[serde_as(as = "DisplayFromStr")]
+    pub strike_price: Num,
+    /// The number of shares of the underlying that one contract is for, usually 100.
+    #[serde_as(as = "DisplayFromStr")]
+    pub multiplier: Num,
+    #[serde_as(as = "DisplayFromStr")]
+    pub size: Num,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub open_interest: Option<Num>,
+    pub open_interest_date: Option<Date>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub close_price: Option<Num>,
+    pub close_price_date: Option<Date>,
+    /// Only present if requested with `show_deliverables`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deliverables: Vec<OptionDeliverable>,
+}
+
+#[derive(Default, Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct OptionContracts {
+    pub option_contracts: Vec<OptionContract>,
+    pub next_page_token: Option<String>,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct OptionTrade {
+    #[serde(rename = "t")]
+    #[serde_as(as = "StreamTimestamp")]
+    pub timestamp: DateTime,
+    #[serde(rename = "x")]
+    pub exchange_code: String,
+    #[serde(rename = "p")]
+    pub price: f64,
+    /// The number of contracts traded.
+    #[serde(rename = "s")]
+    pub size: f64,
+    #[serde(rename = "c")]
+    pub condition: String,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct OptionQuote {
+    #[serde(rename = "t")]
+    #[serde_as(as = "StreamTimestamp")]
+    pub timestamp: DateTime,
+    #[serde(rename = "bx")]
+    pub bid_exchange: String,
+    #[serde(rename = "bp")]
+    pub bid_price: f64,
+    #[serde(rename = "bs")]
+    pub bid_size: f64,
+    #[serde(rename = "ax")]
+    pub ask_exchange: String,
+    #[serde(rename = "ap")]
+    pub ask_price: f64,
+    #[serde(rename = "as")]
+    pub ask_size: f64,
+    #[serde(rename = "c")]
+    pub condition: String,
+}
+
+/// The sensitivities of the price of an option contract.
+#[derive(Default, Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub struct Greeks {
+    pub delta: f64,
+    pub gamma: f64,
+    pub rho: f64,
+    pub theta: f64,
+    pub vega: f64,
+}
+
+/// The latest market data of an option contract, any of which is missing if there is no data
+/// yet.
+#[skip_serializing_none]
+#[derive(Default, Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OptionSnapshot {
+    pub latest_trade: Option<OptionTrade>,
+    pub latest_quote: Option<OptionQuote>,
+    pub minute_bar: Option<HistoricalBar>,
+    pub daily_bar: Option<HistoricalBar>,
+    #[serde(rename = "prevDailyBar")]
+    pub previous_daily_bar: Option<HistoricalBar>,
+    /// The implied volatility, only available with the OPRA feed and if the contract is traded.
+    pub implied_volatility: Option<f64>,
+    /// The greeks, only available with the OPRA feed and if the contract is traded.
+    pub greeks: Option<Greeks>,
+}
+
+#[derive(Default, Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct OptionSnapshots {
+    /// The snapshot of each contract symbol.
+    pub snapshots: HashMap<String, OptionSnapshot>,
+    pub next_page_token: Option<String>,
+}
+
+#[derive(Default, Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct OptionHistoricalTrades {
+    /// The trades of each contract symbol.
+    pub trades: HashMap<String, Vec<OptionTrade>>,
+    pub next_page_token: Option<String>,
+}
+
+#[derive(Default, Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct OptionLatestTrades {
+    pub trades: HashMap<String, OptionTrade>,
+}
+
+#[derive(Default, Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct OptionLatestQuotes {
+    pub quotes: HashMap<String, OptionQuote>,
+}
+
+/// A timestamp that is either an RFC 3339 string, or a MessagePack timestamp as sent by the
+/// real-time data streams when using MessagePack.
+pub(crate) struct StreamTimestamp;
+
+impl SerializeAs<DateTime> for StreamTimestamp {
+    fn serialize_as<S>(source: &DateTime, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        source.serialize(serializer)
+    }
+}
+
+impl<'de> DeserializeAs<'de, DateTime> for StreamTimestamp {
+    fn deserialize_as<D>(deserializer: D) -> Result<DateTime, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = DateTime;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("an RFC 3339 timestamp or a MessagePack timestamp")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<DateTime, E> {
+                v.parse().map_err(E::custom)
+            }
+
+            // rmp-serde passes extension types as a newtype of their type and data
+            fn visit_newtype_struct<D>(self, deserializer: D) -> Result<DateTime, D::Error>
+            where
+                D: serde::de::Deserializer<'de>,
+            {
+                use serde::de::Error;
+
+                let (kind, data) = serde_with::de::DeserializeAsWrap::<
+                    (i8, Vec<u8>),
+                    (serde_with::Same, serde_with::Bytes),
+                >::deserialize(deserializer)?
+                .into_inner();
+                if kind != -1 {
+                    return Err(D::Error::custom(format!(
+                        "unexpected MessagePack extension type {kind}"
+                    )));
+                }
+
+                // https://github.com/msgpack/msgpack/blob/master/spec.md#timestamp-extension-type
+                let (seconds, nanos) = match data.len() {
+                    4 => (u32::from_be_bytes(data[..].try_into().unwrap()).into(), 0),
+                    8 => {
+                        let value = u64::from_be_bytes(data[..].try_into().unwrap());
+                        ((value & 0x3_ffff_ffff) as i64, (value >> 34) as u32)
+                    }
+                    12 => (
+                        i64::from_be_bytes(data[4..].try_into().unwrap()),
+                        u32::from_be_bytes(data[..4].try_into().unwrap()),
+                    ),
+                    len => {
+                        return Err(D::Error::custom(format!(
+                            "invalid MessagePack timestamp length {len}"
+                        )))
+                    }
+                };
+
+                Utc.timestamp_opt(seconds, nanos)
+                    .single()
+                    .ok_or_else(|| D::Error::custom("MessagePack timestamp out of range"))
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+/// Market data of a single symbol, as sent by the real-time data streams.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct SymbolData<T> {
+    #[serde(rename = "S")]
+    pub symbol: String,
+    #[serde(flatten)]
+    pub data: T,
+}
+
+/// The set of symbols subscribed to on each channel of the real-time stock data stream.
+///
+/// `"*"` subscribes to every symbol of a channel.
+#[derive(Default, Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct StockSubscription {
+    pub trades: Vec<String>,
+    pub quotes: Vec<String>,
+    /// Minute bars.
+    pub bars: Vec<String>,
+    pub daily_bars: Vec<String>,
+    /// Minute bars that were updated because of late trades.
+    pub updated_bars: Vec<String>,
+    /// Trading statuses, e.g. halts and resumes.
+    pub statuses: Vec<String>,
+    /// Limit up - limit down price bands.
+    pub lulds: Vec<String>,
+}
+
+/// Implements the methods of a subscription to a real-time data stream, whose fields are the
+/// symbols of each channel.
+macro_rules! impl_subscription {
+    ($($subscription:ident { $($channel:ident),* $(,)? })*) => {$(
+        impl $subscription {
+            /// Whether no channel has any symbols.
+            #[must_use]
+            pub fn is_empty(&self) -> bool {
+                $(self.$channel.is_empty())&&*
+            }
+
+            /// Adds the symbols of each channel of `other` to this subscription.
+            pub fn extend(&mut self, other: &Self) {
+                $(
+                    for symbol in &other.$channel {
+                        if !self.$channel.contains(symbol) {
+                            self.$channel.push(symbol.clone());
+                        }
+                    }
+                )*
+            }
+
+            /// Removes the symbols of each channel of `other` from this subscription.
+            pub fn remove(&mut self, other: &Self) {
+                $(self.$channel.retain(|symbol| !other.$channel.contains(symbol));)*
+            }
+        }
+    )*};
+}
+
+impl_subscription! {
+    StockSubscription {
+        trades,
+        quotes,
+        bars,
+        daily_bars,
+        updated_bars,
+        statuses,
+        lulds,
+    }
+    CryptoSubscription {
+        trades,
+        quotes,
+        bars,
+        daily_bars,
+        updated_bars,
+        orderbooks,
+    }
+}
+
+/// The trading status of a symbol, e.g. a trading halt.
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct TradingStatus {
+    #[serde(rename = "sc")]
+    pub status_code: String,
+    #[serde(rename = "sm")]
+    pub status_message: String,
+    #[serde(rename = "rc")]
+    pub reason_code: String,
+    #[serde(rename = "rm")]
+    pub reason_message: String,
+    #[serde(rename = "t")]
+    #[serde_as(as = "StreamTimestamp")]
+    pub timestamp: DateTime,
+    #[serde(rename = "z")]
+    pub tape: String,
+}
+
+/// The limit up - limit down price band of a symbol.
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Luld {
+    #[serde(rename = "u")]
+    pub limit_up_price: f64,
+    #[serde(rename = "d")]
+    pub limit_down_price: f64,
+    #[serde(rename = "i")]
+    pub indicator: String,
+    #[serde(rename = "t")]
+    #[serde_as(as = "StreamTimestamp")]
+    pub timestamp: DateTime,
+    #[serde(rename = "z")]
+    pub tape: String,
+}
+
+/// A message of the real-time stock data stream.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(tag = "T")]
+pub enum StockStreamMessage {
+    /// The connection or the authentication succeeded.
+    #[serde(rename = "success")]
+    Success { msg: String },
+    /// Something went wrong, e.g. the authentication failed or a subscription is invalid.
+    #[serde(rename = "error")]
+    Error { code: u32, msg: String },
+    /// The current subscription, sent after each (un)subscribe.
+    #[serde(rename = "subscription")]
+    Subscription(StockSubscription),
+    #[serde(rename = "t")]
+    Trade(SymbolData<HistoricalTrade>),
+    #[serde(rename = "q")]
+    Quote(SymbolData<Quote>),
+    #[serde(rename = "b")]
+    Bar(SymbolData<HistoricalBar>),
+    #[serde(rename = "d")]
+    DailyBar(SymbolData<HistoricalBar>),
+    #[serde(rename = "u")]
+    UpdatedBar(SymbolData<HistoricalBar>),
+    #[serde(rename = "s")]
+    Status(SymbolData<TradingStatus>),
+    #[serde(rename = "l")]
+    Luld(SymbolData<Luld>),
+    /// Any other message that we have not accounted for, e.g. trade corrections.
+    #[serde(other)]
+    Unknown,
+}
+
+/// The set of symbols subscribed to on each channel of the real-time crypto data stream.
+///
+/// `"*"` subscribes to every symbol of a channel.
+#[derive(Default, Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CryptoSubscription {
+    pub trades: Vec<String>,
+    pub quotes: Vec<String>,
+    /// Minute bars.
+    pub bars: Vec<String>,
+    pub daily_bars: Vec<String>,
+    /// Minute bars that were updated because of late trades.
+    pub updated_bars: Vec<String>,
+    pub orderbooks: Vec<String>,
+}
+
+/// An update of the order book of a crypto symbol, as sent by the real-time crypto data stream.
+///
+/// A size of `0` removes the price level. See
+/// [`OrderBook`](crate::api::market_data::live::OrderBook) for keeping track of the whole book.
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct CryptoOrderbookUpdate {
+    #[serde(rename = "t")]
+    #[serde_as(as = "StreamTimestamp")]
+    pub timestamp: DateTime,
+    #[serde(rename = "b")]
+    pub bids: Vec<OrderbookEntry>,
+    #[serde(rename = "a")]
+    pub asks: Vec<OrderbookEntry>,
+    /// Whether this is a snapshot of the whole book, which replaces all of its price levels.
+    #[serde(rename = "r", default)]
+    pub reset: bool,
+}
+
+/// A message of the real-time crypto data stream.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(tag = "T")]
+pub enum CryptoStreamMessage {
+    /// The connection or the authentication succeeded.
+    #[serde(rename = "success")]
+    Success { msg: String },
+    /// Something went wrong, e.g. the authentication failed or a subscription is invalid.
+    #[serde(rename = "error")]
+    Error { code: u32, msg: String },
+    /// The current subscription, sent after each (un)subscribe.
+    #[serde(rename = "subscription")]
+    Subscription(CryptoSubscription),
+    #[serde(rename = "t")]
+    Trade(SymbolData<CryptoTrade>),
+    #[serde(rename = "q")]
+    Quote(SymbolData<CryptoQuote>),
+    #[serde(rename = "b")]
+    Bar(SymbolData<HistoricalBar>),
+    #[serde(rename = "d")]
+    DailyBar(SymbolData<HistoricalBar>),
+    #[serde(rename = "u")]
+    UpdatedBar(SymbolData<HistoricalBar>),
+    #[serde(rename = "o")]
+    Orderbook(SymbolData<CryptoOrderbookUpdate>),
+    /// Any other message that we have not accounted for.
+    #[serde(other)]
+    Unknown,
+}
+
+/// The kind of a [`TradeUpdate`].
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum TradeEvent {
+    /// The order has been routed to the exchanges for execution.
+    New,
+    /// The order has been completely filled.
+    Fill,
+    /// A part of the order has been filled.
+    PartialFill,
+    /// The order has been canceled, either by request or by the exchanges.
+    Canceled,
+    /// The order has expired, due to its time in force.
+    Expired,
+    /// The order is done executing for the day.
+    DoneForDay,
+    /// The order has been replaced by a new order.
+    Replaced,
+    /// The order has been rejected.
+    Rejected,
+    /// The order has been received by Alpaca, but not yet routed to the exchanges.
+    PendingNew,
+    /// The order has been stopped, a trade is guaranteed but has not occurred yet.
+    Stopped,
+    /// The order is waiting to be canceled.
+    PendingCancel,
+    /// The order is waiting to be replaced.
+    PendingReplace,
+    /// The order has been completed for the day, settlement calculations are pending.
+    Calculated,
+    /// The order has been suspended, and is not eligible for trading.
+    Suspended,
+    /// Replacing the order was rejected.
+    OrderReplaceRejected,
+    /// Canceling the order was rejected.
+    OrderCancelRejected,
+    /// Any other event that we have not accounted for.
+    #[serde(other, rename(serialize = "unknown"))]
+    Unknown,
+}
+
+/// An update of an order of the account, sent by the trade updates stream.
+#[skip_serializing_none]
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct TradeUpdate {
+    pub event: TradeEvent,
+    /// The order, as of this update.
+    pub order: Order,
+    /// The time of the event, e.g. the time of the execution of a fill.
+    pub timestamp: Option<DateTime>,
+    /// The ID of the execution, for fills and partial fills.
+    pub execution_id: Option<String>,
+    /// The price of the execution, for fills and partial fills.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub price: Option<Num>,
+    /// The quantity of the execution, for fills and partial fills.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub qty: Option<Num>,
+    /// The size of the position after the execution, for fills and partial fills.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub position_qty: Option<Num>,
+}
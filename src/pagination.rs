@@ -1,19 +1,31 @@
 //! A module providing pagination capabilities.
 
+use crate::model::*;
 use acril::Service;
+use futures::future::BoxFuture;
+use futures::Stream;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
 
-pub trait PaginationEndpoint: Service + Sized {
-    type Output;
-    type PageToken;
+/// An endpoint whose output is split into pages.
+///
+/// Everything involved is [`Send`], so that a [`PaginationStream`] can be spawned on a
+/// multi-threaded executor.
+pub trait PaginationEndpoint: Service<Context: Sync, Error: Send> + Sized + Send + Sync {
+    type Output: Send;
+    type PageToken: Send + Sync;
 
-    fn next_page_token(output: &Self::Output) -> Self::PageToken;
+    /// The token of the page after `output`, or [`None`] if `output` is the last page.
+    fn next_page_token(output: &Self::Output) -> Option<Self::PageToken>;
 
-    async fn next(
+    fn next(
         &self,
         previous: Option<Self::PageToken>,
         client: &Self::Context,
         pagination: &PaginationContext<Self>,
-    ) -> Result<Self::Output, Self::Error>;
+    ) -> impl Future<Output = Result<Self::Output, Self::Error>> + Send;
 
     fn paginate<'a>(
         self,
@@ -25,6 +37,7 @@ pub trait PaginationEndpoint: Service + Sized {
             endpoint: self,
             page_size,
             last_page_token: None,
+            exhausted: false,
         }
     }
 }
@@ -34,18 +47,385 @@ pub struct PaginationContext<'a, E: PaginationEndpoint> {
     endpoint: E,
     pub page_size: usize,
     last_page_token: Option<E::PageToken>,
+    exhausted: bool,
 }
 
 impl<E: PaginationEndpoint> PaginationContext<'_, E> {
-    pub async fn next(&mut self) -> Result<E::Output, E::Error> {
+    /// Fetches the next page, or returns [`None`] if the last page has already been fetched.
+    pub async fn next(&mut self) -> Result<Option<E::Output>, E::Error> {
+        if self.exhausted {
+            return Ok(None);
+        }
+
         let last_page_token = self.last_page_token.take();
         let output = self
             .endpoint
             .next(last_page_token, self.client, self)
             .await?;
 
-        self.last_page_token = Some(E::next_page_token(&output));
+        self.last_page_token = E::next_page_token(&output);
+        self.exhausted = self.last_page_token.is_none();
+
+        Ok(Some(output))
+    }
+
+    /// Whether the last page has been fetched.
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+}
+
+impl<'a, E: PaginationEndpoint + 'a> PaginationContext<'a, E>
+where
+    E::Output: Page,
+{
+    /// Turns this into a [`Stream`] of the individual items of all the remaining pages.
+    ///
+    /// If a page can't be fetched, the stream ends with the error, after the items of the pages
+    /// before it.
+    ///
+    /// ```rust,no_run
+    /// # use alpaca_rs::{prelude::*, api::market_data::GetHistoricalBars, pagination::PaginationEndpoint};
+    /// # use futures::StreamExt;
+    /// # async fn run(client: MarketDataClient) -> Result<(), AlpacaError> {
+    /// let mut bars = GetHistoricalBars {
+    ///     symbols: vec!["AAPL".into(), "MSFT".into()],
+    ///     timeframe: Timeframe::Minutes(1),
+    ///     ..Default::default()
+    /// }
+    /// .paginate(10_000, &client)
+    /// .into_stream()
+    /// .prefetch(true)
+    /// .max_items(100_000);
+    ///
+    /// while let Some((symbol, bar)) = bars.next().await.transpose()? {
+    ///     println!("{symbol}: {bar:?}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_stream(self) -> PaginationStream<'a, E> {
+        PaginationStream {
+            items: VecDeque::new(),
+            state: StreamState::Idle(self),
+            error: None,
+            prefetch: false,
+            remaining: None,
+        }
+    }
+}
+
+/// The output of a [`PaginationEndpoint`], which consists of individual items.
+pub trait Page {
+    type Item;
+
+    fn into_items(self) -> Vec<Self::Item>;
+}
+
+impl<T> Page for Vec<T> {
+    type Item = T;
+
+    fn into_items(self) -> Vec<T> {
+        self
+    }
+}
+
+impl Page for HistoricalBars {
+    /// The symbol and the bar.
+    type Item = (String, HistoricalBar);
+
+    fn into_items(self) -> Vec<Self::Item> {
+        self.bars
+            .into_iter()
+            .flat_map(|(symbol, bars)| bars.into_iter().map(move |bar| (symbol.clone(), bar)))
+            .collect()
+    }
+}
+
+impl Page for HistoricalTrades {
+    /// The symbol and the trade.
+    type Item = (String, HistoricalTrade);
+
+    fn into_items(self) -> Vec<Self::Item> {
+        self.trades
+            .into_iter()
+            .flat_map(|(symbol, trades)| {
+                trades.into_iter().map(move |trade| (symbol.clone(), trade))
+            })
+            .collect()
+    }
+}
+
+impl Page for HistoricalQuotes {
+    /// The symbol and the quote.
+    type Item = (String, Quote);
+
+    fn into_items(self) -> Vec<Self::Item> {
+        self.quotes
+            .into_iter()
+            .flat_map(|(symbol, quotes)| {
+                quotes.into_iter().map(move |quote| (symbol.clone(), quote))
+            })
+            .collect()
+    }
+}
+
+impl Page for HistoricalAuctions {
+    /// The symbol and the auction.
+    type Item = (String, HistoricalAuction);
+
+    fn into_items(self) -> Vec<Self::Item> {
+        self.auctions
+            .into_iter()
+            .flat_map(|(symbol, auctions)| {
+                auctions
+                    .into_iter()
+                    .map(move |auction| (symbol.clone(), auction))
+            })
+            .collect()
+    }
+}
+
+impl Page for CryptoHistoricalTrades {
+    /// The symbol and the trade.
+    type Item = (String, CryptoTrade);
+
+    fn into_items(self) -> Vec<Self::Item> {
+        self.trades
+            .into_iter()
+            .flat_map(|(symbol, trades)| {
+                trades.into_iter().map(move |trade| (symbol.clone(), trade))
+            })
+            .collect()
+    }
+}
+
+impl Page for CryptoHistoricalQuotes {
+    /// The symbol and the quote.
+    type Item = (String, CryptoQuote);
+
+    fn into_items(self) -> Vec<Self::Item> {
+        self.quotes
+            .into_iter()
+            .flat_map(|(symbol, quotes)| {
+                quotes.into_iter().map(move |quote| (symbol.clone(), quote))
+            })
+            .collect()
+    }
+}
+
+impl Page for OptionHistoricalTrades {
+    /// The contract symbol and the trade.
+    type Item = (String, OptionTrade);
 
-        Ok(output)
+    fn into_items(self) -> Vec<Self::Item> {
+        self.trades
+            .into_iter()
+            .flat_map(|(symbol, trades)| {
+                trades.into_iter().map(move |trade| (symbol.clone(), trade))
+            })
+            .collect()
     }
 }
+
+impl Page for OptionSnapshots {
+    /// The contract symbol and its snapshot.
+    type Item = (String, OptionSnapshot);
+
+    fn into_items(self) -> Vec<Self::Item> {
+        self.snapshots.into_iter().collect()
+    }
+}
+
+impl Page for OptionContracts {
+    type Item = OptionContract;
+
+    fn into_items(self) -> Vec<Self::Item> {
+        self.option_contracts
+    }
+}
+
+type PageResult<E> = Result<Option<<E as PaginationEndpoint>::Output>, <E as Service>::Error>;
+
+enum StreamState<'a, E: PaginationEndpoint> {
+    Idle(PaginationContext<'a, E>),
+    Fetching(BoxFuture<'a, (PaginationContext<'a, E>, PageResult<E>)>),
+    Done,
+}
+
+/// A [`Stream`] of the individual items of the pages of a [`PaginationEndpoint`], created with
+/// [`PaginationContext::into_stream`].
+#[must_use = "streams do nothing unless polled"]
+pub struct PaginationStream<'a, E: PaginationEndpoint>
+where
+    E::Output: Page,
+{
+    items: VecDeque<<E::Output as Page>::Item>,
+    state: StreamState<'a, E>,
+    /// The error that ended the stream, yielded once the items fetched before it have been.
+    error: Option<E::Error>,
+    prefetch: bool,
+    remaining: Option<usize>,
+}
+
+// no field is ever pinned, the page future is boxed
+impl<E: PaginationEndpoint> Unpin for PaginationStream<'_, E> where E::Output: Page {}
+
+impl<'a, E: PaginationEndpoint + 'a> PaginationStream<'a, E>
+where
+    E::Output: Page,
+{
+    /// Whether to fetch the next page while the items of the current page are being consumed,
+    /// instead of only once they run out. Off by default.
+    pub fn prefetch(mut self, prefetch: bool) -> Self {
+        self.prefetch = prefetch;
+        self
+    }
+
+    /// Stops the stream after this many items, without fetching any further pages.
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.remaining = Some(max_items);
+        self
+    }
+
+    /// Whether a new page should be fetched given the items that are still buffered.
+    fn wants_page(&self, page_size: usize) -> bool {
+        let buffered = self.items.len();
+        let needed = self.remaining.is_none_or(|remaining| buffered < remaining);
+
+        needed && (buffered == 0 || (self.prefetch && buffered <= page_size))
+    }
+}
+
+impl<'a, E: PaginationEndpoint + 'a> Stream for PaginationStream<'a, E>
+where
+    E::Output: Page,
+{
+    type Item = Result<<E::Output as Page>::Item, E::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if this.remaining == Some(0) {
+                this.state = StreamState::Done;
+                return Poll::Ready(None);
+            }
+
+            match std::mem::replace(&mut this.state, StreamState::Done) {
+                StreamState::Idle(pagination) if this.wants_page(pagination.page_size) => {
+                    this.state = StreamState::Fetching(Box::pin(async move {
+                        let mut pagination = pagination;
+                        let page = pagination.next().await;
+                        (pagination, page)
+                    }));
+                    // poll the new future right away to start fetching the page
+                    continue;
+                }
+                StreamState::Fetching(mut fetching) => match fetching.as_mut().poll(cx) {
+                    Poll::Ready((pagination, page)) => {
+                        match page {
+                            Ok(Some(page)) => this.items.extend(page.into_items()),
+                            Ok(None) => {}
+                            // the stream ends with the error, after the items fetched before it
+                            Err(error) => {
+                                this.error = Some(error);
+                                continue;
+                            }
+                        }
+                        if !pagination.is_exhausted() {
+                            this.state = StreamState::Idle(pagination);
+                        }
+                        continue;
+                    }
+                    Poll::Pending => this.state = StreamState::Fetching(fetching),
+                },
+                state => this.state = state,
+            }
+
+            return match this.items.pop_front() {
+                Some(item) => {
+                    if let Some(remaining) = &mut this.remaining {
+                        *remaining -= 1;
+                    }
+                    Poll::Ready(Some(Ok(item)))
+                }
+                None if this.error.is_some() => Poll::Ready(this.error.take().map(Err)),
+                None if matches!(this.state, StreamState::Done) => Poll::Ready(None),
+                None => Poll::Pending,
+            };
+        }
+    }
+}
+
+/// Implements [`PaginationEndpoint`] for endpoints that take a `page_token` and a `limit` (the
+/// page size), and whose output has a `next_page_token`.
+macro_rules! impl_page_token_pagination {
+    ($($endpoint:ty => $output:ty),* $(,)?) => {$(
+        impl $crate::pagination::PaginationEndpoint for $endpoint {
+            type Output = $output;
+            type PageToken = String;
+
+            fn next_page_token(output: &Self::Output) -> Option<String> {
+                output.next_page_token.clone()
+            }
+
+            async fn next(
+                &self,
+                previous: Option<String>,
+                client: &Self::Context,
+                pagination: &$crate::pagination::PaginationContext<'_, Self>,
+            ) -> $crate::Result<Self::Output> {
+                Self {
+                    page_token: previous,
+                    limit: pagination.page_size.try_into().ok(),
+                    ..self.clone()
+                }
+                .run(client)
+                .await
+            }
+        }
+    )*};
+}
+
+pub(crate) use impl_page_token_pagination;
+
+/// Implements [`PaginationEndpoint`] for endpoints that return a list of [`Identifiable`]
+/// items, whose next page starts after the ID of the last item (`page_token`), and whose size is
+/// set with `page_size`.
+///
+/// The API does not tell whether there are more pages, so the pagination only ends when a page
+/// is empty.
+///
+/// [`Identifiable`]: crate::Identifiable
+macro_rules! impl_identifiable_pagination {
+    ($($endpoint:ty => $item:ty),* $(,)?) => {$(
+        impl $crate::pagination::PaginationEndpoint for $endpoint {
+            type Output = Vec<$item>;
+            type PageToken = String;
+
+            fn next_page_token(output: &Self::Output) -> Option<String> {
+                output
+                    .last()
+                    .and_then($crate::Identifiable::next_page_token)
+            }
+
+            async fn next(
+                &self,
+                previous: Option<String>,
+                client: &Self::Context,
+                pagination: &$crate::pagination::PaginationContext<'_, Self>,
+            ) -> $crate::Result<Self::Output> {
+                Self {
+                    page_token: previous,
+                    page_size: pagination.page_size.try_into().ok(),
+                    ..self.clone()
+                }
+                .run(client)
+                .await
+            }
+        }
+    )*};
+}
+
+pub(crate) use impl_identifiable_pagination;
@@ -0,0 +1,36 @@
+mod common;
+
+use alpaca_rs::prelude::*;
+use common::num;
+
+#[test]
+fn trading_account() {
+    let account: Account =
+        serde_json::from_str(include_str!("fixtures/account/trading.json")).unwrap();
+
+    assert_eq!(account.status, AccountStatus::Active);
+    assert_eq!(account.equity, Some(num("100450.12")));
+    assert_eq!(account.last_equity, num("100012.5"));
+    assert_eq!(account.daytrading_buying_power, Some(num("369063.24")));
+    assert_eq!(account.multiplier, Some(num("2")));
+    assert_eq!(account.last_maintenance_margin, Some(num("2431.5")));
+    assert_eq!(account.daytrade_count, Some(1));
+    assert_eq!(account.pattern_day_trader, Some(false));
+    assert_eq!(account.options_trading_level, Some(2));
+
+    let reparsed: Account =
+        serde_json::from_value(serde_json::to_value(&account).unwrap()).unwrap();
+    assert_eq!(reparsed, account);
+}
+
+#[test]
+fn broker_account() {
+    let account: Account =
+        serde_json::from_str(include_str!("fixtures/account/broker.json")).unwrap();
+
+    assert_eq!(account.account_number, "935142145");
+    assert_eq!(account.last_equity, num("10012.5"));
+    assert_eq!(account.buying_power, num("4531.62"));
+    assert_eq!(account.equity, None);
+    assert_eq!(account.trading_blocked, None);
+}
@@ -0,0 +1,74 @@
+//! Recorded account activities, which are told apart by their fields and grouped by their type
+//! code.
+mod common;
+
+use alpaca_rs::chrono::NaiveDate;
+use alpaca_rs::prelude::*;
+use common::num;
+
+/// Parses a recorded activity, and checks that it survives serializing and deserializing again.
+fn parse(json: &str) -> Activity {
+    let activity: Activity = serde_json::from_str(json).unwrap();
+
+    let serialized = serde_json::to_string(&activity).unwrap();
+    let reparsed: Activity = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(reparsed, activity, "serialized as {serialized}");
+
+    activity
+}
+
+#[test]
+fn fill() {
+    let Activity::Fill(fill) = parse(include_str!("fixtures/activities/fill.json")) else {
+        panic!("expected a fill");
+    };
+
+    assert_eq!(fill.activity_type, ActivityType::Fill);
+    assert_eq!(fill.fill_type, FillType::PartialFill);
+    assert_eq!(fill.side, FillSide::SellShort);
+    assert_eq!(fill.symbol, "AMZN");
+    assert_eq!(fill.price, num("3009.12"));
+    assert_eq!(fill.qty, num("1"));
+    assert_eq!(fill.leaves_qty, num("2"));
+    assert_eq!(fill.cum_qty, num("1"));
+    assert_eq!(fill.order_status, OrderStatus::PartiallyFilled);
+}
+
+#[test]
+fn dividend() {
+    let activity = parse(include_str!("fixtures/activities/dividend.json"));
+    assert_eq!(activity.activity_type(), &ActivityType::Dividend);
+    let Activity::Dividend(dividend) = activity else {
+        panic!("expected a dividend, got {activity:?}");
+    };
+
+    assert_eq!(dividend.date, NaiveDate::from_ymd_opt(2019, 8, 1).unwrap());
+    assert_eq!(dividend.net_amount, num("1.02"));
+    assert_eq!(dividend.symbol.as_deref(), Some("T"));
+    assert_eq!(dividend.qty, Some(num("2")));
+    assert_eq!(dividend.per_share_amount, Some(num("0.51")));
+    assert_eq!(dividend.activity_sub_type, None);
+}
+
+#[test]
+fn unknown_type() {
+    let activity = parse(include_str!("fixtures/activities/voluntary_offer.json"));
+    let Activity::Other(other) = activity else {
+        panic!("expected another activity, got {activity:?}");
+    };
+
+    assert_eq!(other.activity_type, ActivityType::Other("VOF".to_owned()));
+    assert_eq!(other.activity_type.code(), "VOF");
+    assert_eq!(other.activity_sub_type.as_deref(), Some("VOF"));
+    assert_eq!(other.qty, Some(num("-10")));
+}
+
+#[test]
+fn incomplete_fill_is_an_error() {
+    let mut fill: serde_json::Value =
+        serde_json::from_str(include_str!("fixtures/activities/fill.json")).unwrap();
+    fill.as_object_mut().unwrap().remove("order_status");
+
+    // a fill has none of the fields of the other activities, so it can't be mistaken for one
+    assert!(serde_json::from_value::<Activity>(fill).is_err());
+}
@@ -0,0 +1,290 @@
+//! Local stand-ins for the Alpaca servers, shared by the integration tests.
+#![allow(dead_code)]
+
+use alpaca_rs::prelude::*;
+use async_std::io::{prelude::BufReadExt, BufReader, ReadExt, WriteExt};
+use async_std::net::{SocketAddr, TcpListener, TcpStream};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+#[cfg(any(feature = "market-data-live", feature = "trading-live"))]
+pub mod stream;
+
+/// Parses a number, which is exact with the `decimal` feature.
+pub fn num(s: &str) -> Num {
+    s.parse().unwrap()
+}
+
+pub fn auth() -> TradingAuth {
+    TradingAuth {
+        key_id: "key".to_owned(),
+        secret: "secret".to_owned(),
+    }
+}
+
+/// A trading client of `server`, which does not retry failed requests.
+pub fn trading_client(server: &MockServer) -> TradingClient {
+    TradingClient::new(auth(), server.url("/v2/").parse().unwrap())
+        .with_retry_policy(RetryPolicy::none())
+}
+
+/// A market data client of `server`, which does not retry failed requests.
+pub fn market_data_client(server: &MockServer) -> MarketDataClient {
+    MarketDataClient::new(auth(), server.url("/v2").parse().unwrap())
+        .with_retry_policy(RetryPolicy::none())
+}
+
+/// A request received by a [`MockServer`].
+#[derive(Debug, Clone)]
+pub struct Received {
+    pub method: String,
+    /// The path and the query of the request.
+    pub target: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    pub at: SystemTime,
+}
+
+impl Received {
+    pub fn path(&self) -> &str {
+        self.target.split('?').next().unwrap()
+    }
+
+    /// The decoded value of a query parameter.
+    pub fn query(&self, name: &str) -> Option<String> {
+        let (_, query) = self.target.split_once('?')?;
+        form_urlencoded_pairs(query)
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value)
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn json(&self) -> serde_json::Value {
+        serde_json::from_slice(&self.body).unwrap()
+    }
+}
+
+fn form_urlencoded_pairs(query: &str) -> impl Iterator<Item = (String, String)> + '_ {
+    fn decode(s: &str) -> String {
+        let bytes = s.as_bytes();
+        let mut decoded = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'+' => decoded.push(b' '),
+                b'%' if i + 2 < bytes.len() => {
+                    decoded.push(u8::from_str_radix(&s[i + 1..i + 3], 16).unwrap());
+                    i += 2;
+                }
+                byte => decoded.push(byte),
+            }
+            i += 1;
+        }
+        String::from_utf8(decoded).unwrap()
+    }
+
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode(key), decode(value))
+        })
+}
+
+/// A response sent by a [`MockServer`].
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl MockResponse {
+    /// A successful response with a JSON body.
+    pub fn json(body: impl Into<String>) -> Self {
+        Self {
+            status: 200,
+            headers: vec![("Content-Type".to_owned(), "application/json".to_owned())],
+            body: body.into(),
+        }
+    }
+
+    /// A successful response without a body.
+    pub fn empty() -> Self {
+        Self::status(204)
+    }
+
+    /// A response with the given status, and without a body.
+    pub fn status(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: String::new(),
+        }
+    }
+
+    /// An error response of the Alpaca API.
+    pub fn error(status: u16, code: u32, message: &str) -> Self {
+        Self {
+            status,
+            ..Self::json(serde_json::json!({ "code": code, "message": message }).to_string())
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: impl ToString) -> Self {
+        self.headers.push((name.to_owned(), value.to_string()));
+        self
+    }
+}
+
+/// A local HTTP server that responds with canned responses, and records the requests.
+pub struct MockServer {
+    addr: SocketAddr,
+    requests: Arc<Mutex<Vec<Received>>>,
+}
+
+impl MockServer {
+    /// Starts a server that sends `responses` in order, repeating the last one once they run out.
+    pub async fn start(responses: Vec<MockResponse>) -> Self {
+        assert!(!responses.is_empty(), "a mock server needs a response");
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let responses = Arc::new(Mutex::new(VecDeque::from(responses)));
+
+        let recorded = requests.clone();
+        async_std::task::spawn(async move {
+            loop {
+                let Ok((stream, _)) = listener.accept().await else {
+                    return;
+                };
+                async_std::task::spawn(serve(stream, recorded.clone(), responses.clone()));
+            }
+        });
+
+        Self { addr, requests }
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("http://{}{path}", self.addr)
+    }
+
+    /// The requests received so far, in order.
+    pub fn requests(&self) -> Vec<Received> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// The method and the path of each request received so far.
+    pub fn paths(&self) -> Vec<(String, String)> {
+        self.requests()
+            .into_iter()
+            .map(|request| (request.method.clone(), request.path().to_owned()))
+            .collect()
+    }
+}
+
+async fn serve(
+    stream: TcpStream,
+    requests: Arc<Mutex<Vec<Received>>>,
+    responses: Arc<Mutex<VecDeque<MockResponse>>>,
+) {
+    let mut reader = BufReader::new(stream.clone());
+    let mut writer = stream;
+
+    loop {
+        let Some(request) = read_request(&mut reader).await else {
+            return;
+        };
+        requests.lock().unwrap().push(request);
+
+        let response = {
+            let mut responses = responses.lock().unwrap();
+            if responses.len() > 1 {
+                responses.pop_front().unwrap()
+            } else {
+                responses[0].clone()
+            }
+        };
+
+        let mut head = format!(
+            "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\n",
+            response.status,
+            response.body.len()
+        );
+        for (name, value) in &response.headers {
+            head.push_str(&format!("{name}: {value}\r\n"));
+        }
+        head.push_str("\r\n");
+
+        if writer.write_all(head.as_bytes()).await.is_err()
+            || writer.write_all(response.body.as_bytes()).await.is_err()
+            || writer.flush().await.is_err()
+        {
+            return;
+        }
+    }
+}
+
+async fn read_request(reader: &mut BufReader<TcpStream>) -> Option<Received> {
+    let mut line = String::new();
+    if reader.read_line(&mut line).await.ok()? == 0 {
+        return None;
+    }
+    let mut request_line = line.split_whitespace();
+    let method = request_line.next()?.to_owned();
+    let target = request_line.next()?.to_owned();
+
+    let mut headers = Vec::new();
+    loop {
+        line.clear();
+        reader.read_line(&mut line).await.ok()?;
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let (name, value) = header.split_once(':')?;
+        headers.push((name.trim().to_owned(), value.trim().to_owned()));
+    }
+
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(key, _): &&(String, String)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.clone())
+    };
+
+    let mut body = Vec::new();
+    if header("Transfer-Encoding").is_some_and(|encoding| encoding.contains("chunked")) {
+        loop {
+            line.clear();
+            reader.read_line(&mut line).await.ok()?;
+            let size = usize::from_str_radix(line.trim(), 16).ok()?;
+            let mut chunk = vec![0; size + 2];
+            reader.read_exact(&mut chunk).await.ok()?;
+            if size == 0 {
+                break;
+            }
+            body.extend_from_slice(&chunk[..size]);
+        }
+    } else if let Some(length) = header("Content-Length").and_then(|x| x.parse().ok()) {
+        body.resize(length, 0);
+        reader.read_exact(&mut body).await.ok()?;
+    }
+
+    Some(Received {
+        method,
+        target,
+        headers,
+        body,
+        at: SystemTime::now(),
+    })
+}
@@ -0,0 +1,249 @@
+//! A local stand-in for the real-time data streams.
+use async_std::net::{SocketAddr, TcpListener};
+use async_tungstenite::{accept_async, tungstenite::Message};
+use futures::channel::mpsc::{unbounded, UnboundedSender};
+use futures::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+enum Command {
+    Send(String),
+    Disconnect,
+}
+
+enum Event {
+    Received(Message),
+    Command(Command),
+}
+
+#[derive(Default)]
+struct State {
+    /// The actions received on each connection.
+    connections: Vec<Vec<Value>>,
+    /// The commands to the current connection.
+    current: Option<UnboundedSender<Command>>,
+}
+
+/// The stream a [`MockStream`] stands in for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Protocol {
+    /// A real-time market data stream, which confirms each (un)subscribe with the resulting
+    /// subscription.
+    MarketData,
+    /// The trade updates stream, which confirms a listen with the streams listened to.
+    Trading,
+}
+
+/// A local stream that accepts the auth of [`auth`](super::auth) and answers the actions of
+/// the client like the real one does.
+pub struct MockStream {
+    addr: SocketAddr,
+    protocol: Protocol,
+    state: Arc<Mutex<State>>,
+}
+
+impl MockStream {
+    /// A real-time market data stream.
+    pub async fn start() -> Self {
+        Self::start_with(Protocol::MarketData).await
+    }
+
+    /// The trade updates stream.
+    pub async fn start_trading() -> Self {
+        Self::start_with(Protocol::Trading).await
+    }
+
+    async fn start_with(protocol: Protocol) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let shared = state.clone();
+        async_std::task::spawn(async move {
+            loop {
+                let Ok((stream, _)) = listener.accept().await else {
+                    return;
+                };
+                let Ok(socket) = accept_async(stream).await else {
+                    continue;
+                };
+                async_std::task::spawn(serve(socket, protocol, shared.clone()));
+            }
+        });
+
+        Self {
+            addr,
+            protocol,
+            state,
+        }
+    }
+
+    pub fn url(&self) -> String {
+        match self.protocol {
+            Protocol::MarketData => format!("ws://{}/v2/iex", self.addr),
+            Protocol::Trading => format!("ws://{}/stream", self.addr),
+        }
+    }
+
+    /// Sends a frame to the current connection.
+    pub async fn send(&self, frame: Value) {
+        self.wait_for_connection().await;
+        self.command(Command::Send(frame.to_string()));
+    }
+
+    /// Drops the current connection, without a close frame.
+    pub async fn disconnect(&self) {
+        self.wait_for_connection().await;
+        if let Some(current) = self.state.lock().unwrap().current.take() {
+            let _ = current.unbounded_send(Command::Disconnect);
+        }
+    }
+
+    /// The actions received on each connection so far.
+    pub fn connections(&self) -> Vec<Vec<Value>> {
+        self.state.lock().unwrap().connections.clone()
+    }
+
+    /// Waits until `n` actions have been received on the current connection, and returns them.
+    pub async fn actions(&self, n: usize) -> Vec<Value> {
+        for _ in 0..200 {
+            if let Some(actions) = self.connections().pop() {
+                if actions.len() >= n {
+                    return actions;
+                }
+            }
+            async_std::task::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("expected {n} actions, got {:?}", self.connections());
+    }
+
+    async fn wait_for_connection(&self) {
+        for _ in 0..200 {
+            if self.state.lock().unwrap().current.is_some() {
+                return;
+            }
+            async_std::task::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("no client connected");
+    }
+
+    fn command(&self, command: Command) {
+        if let Some(current) = &self.state.lock().unwrap().current {
+            let _ = current.unbounded_send(command);
+        }
+    }
+}
+
+async fn serve<S>(
+    socket: async_tungstenite::WebSocketStream<S>,
+    protocol: Protocol,
+    state: Arc<Mutex<State>>,
+) where
+    S: futures::AsyncRead + futures::AsyncWrite + Unpin,
+{
+    let (mut sink, source) = socket.split();
+    let (sender, receiver) = unbounded();
+    let connection = {
+        let mut state = state.lock().unwrap();
+        state.connections.push(Vec::new());
+        state.current = Some(sender);
+        state.connections.len() - 1
+    };
+
+    let mut events = futures::stream::select(
+        source
+            .take_while(|message| futures::future::ready(message.is_ok()))
+            .map(|message| Event::Received(message.unwrap())),
+        receiver.map(Event::Command),
+    );
+    let mut subscription = Subscription::default();
+
+    // only the market data streams greet a new connection
+    let connected = json!([{ "T": "success", "msg": "connected" }]);
+    if protocol == Protocol::MarketData
+        && sink
+            .send(Message::Text(connected.to_string()))
+            .await
+            .is_err()
+    {
+        return;
+    }
+
+    while let Some(event) = events.next().await {
+        let frame = match event {
+            Event::Received(Message::Text(text)) => {
+                let action: Value = serde_json::from_str(&text).unwrap();
+                state.lock().unwrap().connections[connection].push(action.clone());
+                match protocol {
+                    Protocol::MarketData => reply(&action, &mut subscription),
+                    Protocol::Trading => reply_trading(&action),
+                }
+            }
+            Event::Received(Message::Close(_)) => return,
+            Event::Received(_) => continue,
+            Event::Command(Command::Send(frame)) => frame,
+            Event::Command(Command::Disconnect) => return,
+        };
+        if sink.send(Message::Text(frame)).await.is_err() {
+            return;
+        }
+    }
+}
+
+/// The symbols of each channel, by the name of the channel.
+type Subscription = serde_json::Map<String, Value>;
+
+fn reply(action: &Value, subscription: &mut Subscription) -> String {
+    let reply = match action["action"].as_str() {
+        Some("auth") if action["key"] == "key" && action["secret"] == "secret" => {
+            json!({ "T": "success", "msg": "authenticated" })
+        }
+        Some("auth") => json!({ "T": "error", "code": 402, "msg": "auth failed" }),
+        Some(change @ ("subscribe" | "unsubscribe")) => {
+            for (channel, symbols) in action.as_object().unwrap() {
+                let Some(symbols) = symbols.as_array() else {
+                    continue;
+                };
+                let subscribed = subscription
+                    .entry(channel.clone())
+                    .or_insert_with(|| json!([]))
+                    .as_array_mut()
+                    .unwrap();
+                for symbol in symbols {
+                    subscribed.retain(|subscribed| subscribed != symbol);
+                    if change == "subscribe" {
+                        subscribed.push(symbol.clone());
+                    }
+                }
+            }
+            let mut reply = subscription.clone();
+            reply.insert("T".to_owned(), json!("subscription"));
+            Value::Object(reply)
+        }
+        _ => json!({ "T": "error", "code": 400, "msg": "invalid syntax" }),
+    };
+    json!([reply]).to_string()
+}
+
+fn reply_trading(action: &Value) -> String {
+    let reply = match action["action"].as_str() {
+        Some("auth") => {
+            let status = if action["key"] == "key" && action["secret"] == "secret" {
+                "authorized"
+            } else {
+                "unauthorized"
+            };
+            json!({
+                "stream": "authorization",
+                "data": { "status": status, "action": "authenticate" },
+            })
+        }
+        Some("listen") => json!({
+            "stream": "listening",
+            "data": { "streams": action["data"]["streams"] },
+        }),
+        _ => json!({ "stream": "error", "data": { "error_message": "invalid syntax" } }),
+    };
+    reply.to_string()
+}
@@ -0,0 +1,179 @@
+//! Runs the [`LiveClient`] against a local stand-in for the real-time stock data stream.
+#![cfg(feature = "market-data-live")]
+mod common;
+
+use alpaca_rs::api::market_data::live::{Encoding, LiveClient};
+use alpaca_rs::prelude::*;
+use common::{auth, stream::MockStream};
+use futures::StreamExt;
+use serde_json::json;
+
+async fn connect(stream: &MockStream) -> LiveClient {
+    LiveClient::connect(&auth(), stream.url().parse().unwrap(), Encoding::Json)
+        .await
+        .unwrap()
+}
+
+async fn next(client: &mut LiveClient) -> StockStreamMessage {
+    client.next().await.unwrap().unwrap()
+}
+
+fn symbols(symbols: &[&str]) -> Vec<String> {
+    symbols.iter().map(|&symbol| symbol.to_owned()).collect()
+}
+
+#[async_std::test]
+async fn connects_authenticates_and_subscribes() {
+    let stream = MockStream::start().await;
+    let mut client = connect(&stream).await;
+
+    client
+        .subscribe(&StockSubscription {
+            trades: symbols(&["AAPL"]),
+            bars: symbols(&["*"]),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let actions = stream.actions(2).await;
+    assert_eq!(
+        actions[0],
+        json!({ "action": "auth", "key": "key", "secret": "secret" })
+    );
+    assert_eq!(actions[1]["action"], "subscribe");
+    assert_eq!(actions[1]["trades"], json!(["AAPL"]));
+    assert_eq!(actions[1]["bars"], json!(["*"]));
+    assert_eq!(actions[1]["quotes"], json!([]));
+}
+
+#[async_std::test]
+async fn fails_to_authenticate() {
+    let stream = MockStream::start().await;
+    let auth = TradingAuth {
+        secret: "wrong".to_owned(),
+        ..auth()
+    };
+
+    let result = LiveClient::<StockStreamMessage>::connect(
+        &auth,
+        stream.url().parse().unwrap(),
+        Encoding::Json,
+    )
+    .await;
+
+    match result {
+        Err(AlpacaError::Stream { code, message }) => {
+            assert_eq!(code, Some(402));
+            assert_eq!(message, "auth failed");
+        }
+        Err(error) => panic!("expected a stream error, got {error:?}"),
+        Ok(_) => panic!("expected the authentication to fail"),
+    }
+}
+
+#[async_std::test]
+async fn confirmed_subscription() {
+    let stream = MockStream::start().await;
+    let mut client = connect(&stream).await;
+
+    client
+        .subscribe(&StockSubscription {
+            trades: symbols(&["AAPL"]),
+            quotes: symbols(&["MSFT"]),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(client.subscription(), &StockSubscription::default());
+
+    let StockStreamMessage::Subscription(confirmed) = next(&mut client).await else {
+        panic!("expected a subscription confirmation");
+    };
+    assert_eq!(confirmed.trades, ["AAPL"]);
+    assert_eq!(client.subscription(), &confirmed);
+
+    // the stream has the last word, e.g. when it drops symbols
+    stream
+        .send(json!([{ "T": "subscription", "trades": ["AAPL", "TSLA"] }]))
+        .await;
+    next(&mut client).await;
+    assert_eq!(
+        client.subscription(),
+        &StockSubscription {
+            trades: symbols(&["AAPL", "TSLA"]),
+            ..Default::default()
+        }
+    );
+}
+
+#[async_std::test]
+async fn frames_with_several_messages() {
+    let stream = MockStream::start().await;
+    let mut client = connect(&stream).await;
+
+    stream
+        .send(json!([
+            {
+                "T": "t",
+                "S": "AAPL",
+                "i": 52983525029461_i64,
+                "x": "V",
+                "p": 184.22,
+                "s": 100,
+                "c": ["@"],
+                "t": "2024-01-03T14:30:00.004285Z",
+                "z": "C"
+            },
+            {
+                "T": "q",
+                "S": "MSFT",
+                "bx": "V",
+                "bp": 368.95,
+                "bs": 3,
+                "ax": "V",
+                "ap": 369.1,
+                "as": 1,
+                "c": ["R"],
+                "z": "C",
+                "t": "2024-01-03T14:30:00.000845Z"
+            },
+            { "T": "success", "msg": "still here" }
+        ]))
+        .await;
+    stream
+        .send(json!([{
+            "T": "b",
+            "S": "AAPL",
+            "o": 184.22,
+            "h": 184.5,
+            "l": 184.1,
+            "c": 184.3,
+            "v": 12345,
+            "n": 120,
+            "vw": 184.27,
+            "t": "2024-01-03T14:30:00Z"
+        }]))
+        .await;
+
+    let StockStreamMessage::Trade(trade) = next(&mut client).await else {
+        panic!("expected a trade");
+    };
+    assert_eq!(trade.symbol, "AAPL");
+    assert_eq!(trade.data.price, 184.22);
+    let StockStreamMessage::Quote(quote) = next(&mut client).await else {
+        panic!("expected a quote");
+    };
+    assert_eq!(quote.symbol, "MSFT");
+    assert_eq!(quote.data.bid_size, 3.0);
+    assert_eq!(
+        next(&mut client).await,
+        StockStreamMessage::Success {
+            msg: "still here".to_owned()
+        }
+    );
+    let StockStreamMessage::Bar(bar) = next(&mut client).await else {
+        panic!("expected a bar");
+    };
+    assert_eq!(bar.symbol, "AAPL");
+}
@@ -0,0 +1,130 @@
+mod common;
+
+use alpaca_rs::api::market_data::{ContractSymbols, GetOptionHistoricalBars, GetOptionSnapshots};
+use alpaca_rs::chrono::{Duration, NaiveDate};
+use alpaca_rs::prelude::*;
+use common::{market_data_client, num, MockResponse, MockServer};
+use proptest::prelude::*;
+
+/// A strike price from its thousandths of a dollar, as encoded in a symbol.
+fn strike(thousandths: u32) -> Num {
+    num(&format!("{}.{:03}", thousandths / 1000, thousandths % 1000))
+}
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+proptest! {
+    #[test]
+    fn formats_and_parses_back(
+        underlying in "[A-Z]{1,5}[0-9]?",
+        days in 0..25_000i64,
+        call: bool,
+        thousandths in 0..=99_999_999u32,
+    ) {
+        let expiration = date(2000, 1, 1) + Duration::days(days);
+        let kind = if call { OptionType::Call } else { OptionType::Put };
+        let symbol = OptionSymbol::new(&underlying, expiration, kind, strike(thousandths)).unwrap();
+
+        let formatted = symbol.to_string();
+        prop_assert_eq!(formatted.len(), underlying.len() + 15);
+        let parsed: OptionSymbol = formatted.parse().unwrap();
+        prop_assert_eq!(&parsed, &symbol);
+        prop_assert_eq!(parsed.underlying(), underlying.as_str());
+        prop_assert_eq!(parsed.expiration(), expiration);
+        prop_assert_eq!(parsed.kind(), kind);
+        prop_assert_eq!(parsed.strike(), strike(thousandths));
+    }
+
+    #[test]
+    fn parses_and_formats_back(
+        symbol in "[A-Z]{1,6}[0-6][0-9](0[1-9]|1[0-2])(0[1-9]|1[0-9]|2[0-8])[CP][0-9]{8}",
+    ) {
+        let parsed: OptionSymbol = symbol.parse().unwrap();
+        prop_assert_eq!(parsed.to_string(), symbol);
+    }
+
+    #[test]
+    fn serializes_as_the_symbol(thousandths in 0..=99_999_999u32) {
+        let symbol =
+            OptionSymbol::new("SPY", date(2024, 3, 15), OptionType::Put, strike(thousandths))
+                .unwrap();
+
+        let json = serde_json::to_value(&symbol).unwrap();
+        prop_assert_eq!(json.as_str(), Some(symbol.to_string().as_str()));
+        prop_assert_eq!(serde_json::from_value::<OptionSymbol>(json).unwrap(), symbol);
+    }
+}
+
+#[test]
+fn rejects_what_does_not_fit() {
+    let new = |underlying: &str, expiration, strike: &str| {
+        OptionSymbol::new(underlying, expiration, OptionType::Call, num(strike))
+    };
+    let expiration = date(2024, 1, 19);
+
+    assert!(new("AAPL", expiration, "99999.999").is_ok());
+    assert_eq!(
+        new("AAPL", expiration, "100000"),
+        Err("invalid strike price")
+    );
+    assert_eq!(new("AAPL", expiration, "-1"), Err("invalid strike price"));
+    assert_eq!(
+        new("AAPL", expiration, "150.0005"),
+        Err("invalid strike price")
+    );
+    assert_eq!(new("", expiration, "150"), Err("no underlying symbol"));
+    assert_eq!(
+        new("BRK B", expiration, "150"),
+        Err("invalid underlying symbol")
+    );
+    assert_eq!(
+        new("AAPL", date(1999, 12, 17), "150"),
+        Err("invalid expiration date")
+    );
+    assert_eq!(
+        new("AAPL", date(2069, 1, 18), "150"),
+        Err("invalid expiration date")
+    );
+
+    // a ninth digit of the strike price is taken for the underlying
+    assert_ne!(
+        "AAPL240119C100000000"
+            .parse::<OptionSymbol>()
+            .map(|symbol| symbol.strike()),
+        Ok(num("100000"))
+    );
+    assert!("AAPL240119C0015000".parse::<OptionSymbol>().is_err());
+    assert!("AAPL240119X00150000".parse::<OptionSymbol>().is_err());
+}
+
+#[async_std::test]
+async fn option_data_of_symbols() {
+    let server = MockServer::start(vec![MockResponse::json(r#"{"snapshots":{}}"#)]).await;
+    let client = market_data_client(&server);
+    let call: OptionSymbol = "AAPL240119C00150000".parse().unwrap();
+    let put = OptionSymbol::new("AAPL", date(2024, 1, 19), OptionType::Put, num("147.5")).unwrap();
+
+    let _ = client
+        .execute(GetOptionSnapshots::for_contracts([call.clone(), put]))
+        .await;
+    let _ = client
+        .execute(GetOptionHistoricalBars {
+            timeframe: Timeframe::Day,
+            ..GetOptionHistoricalBars::for_contracts([call])
+        })
+        .await;
+
+    let requests = server.requests();
+    assert_eq!(requests[0].path(), "/v1beta1/options/snapshots");
+    assert_eq!(
+        requests[0].query("symbols").as_deref(),
+        Some("AAPL240119C00150000,AAPL240119P00147500")
+    );
+    assert_eq!(requests[1].path(), "/v1beta1/options/bars");
+    assert_eq!(
+        requests[1].query("symbols").as_deref(),
+        Some("AAPL240119C00150000")
+    );
+}
@@ -0,0 +1,149 @@
+//! Keeps an [`OrderBook`] up to date with the order book updates of the crypto data stream.
+#![cfg(feature = "market-data-live")]
+
+use alpaca_rs::api::market_data::live::OrderBook;
+use alpaca_rs::chrono::{DateTime, TimeZone, Utc};
+use alpaca_rs::prelude::*;
+
+fn levels(levels: &[(f64, f64)]) -> Vec<OrderbookEntry> {
+    levels
+        .iter()
+        .map(|&(price, size)| OrderbookEntry { price, size })
+        .collect()
+}
+
+fn at(second: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 1, 2, 15, 0, second).unwrap()
+}
+
+fn update(second: u32, bids: &[(f64, f64)], asks: &[(f64, f64)]) -> CryptoOrderbookUpdate {
+    CryptoOrderbookUpdate {
+        timestamp: at(second),
+        bids: levels(bids),
+        asks: levels(asks),
+        reset: false,
+    }
+}
+
+fn snapshot(second: u32, bids: &[(f64, f64)], asks: &[(f64, f64)]) -> CryptoOrderbookUpdate {
+    CryptoOrderbookUpdate {
+        reset: true,
+        ..update(second, bids, asks)
+    }
+}
+
+fn book(updates: &[CryptoOrderbookUpdate]) -> OrderBook {
+    let mut book = OrderBook::default();
+    for update in updates {
+        book.apply(update);
+    }
+    book
+}
+
+fn prices(entries: impl Iterator<Item = OrderbookEntry>) -> Vec<f64> {
+    entries.map(|entry| entry.price).collect()
+}
+
+#[test]
+fn snapshot_replaces_the_book() {
+    let book = book(&[
+        snapshot(0, &[(42000.0, 1.0), (41990.0, 2.0)], &[(42010.0, 0.5)]),
+        snapshot(1, &[(43000.0, 0.25)], &[(43020.0, 0.75)]),
+    ]);
+
+    assert_eq!(book.bids().collect::<Vec<_>>(), levels(&[(43000.0, 0.25)]));
+    assert_eq!(book.asks().collect::<Vec<_>>(), levels(&[(43020.0, 0.75)]));
+    assert_eq!(book.timestamp(), Some(at(1)));
+}
+
+#[test]
+fn update_changes_single_levels() {
+    let book = book(&[
+        snapshot(0, &[(42000.0, 1.0), (41990.0, 2.0)], &[(42010.0, 0.5)]),
+        update(1, &[(42000.0, 1.5), (41995.0, 0.1)], &[]),
+    ]);
+
+    assert_eq!(
+        book.bids().collect::<Vec<_>>(),
+        levels(&[(42000.0, 1.5), (41995.0, 0.1), (41990.0, 2.0)])
+    );
+    assert_eq!(book.asks().collect::<Vec<_>>(), levels(&[(42010.0, 0.5)]));
+    assert_eq!(book.timestamp(), Some(at(1)));
+}
+
+#[test]
+fn size_zero_removes_the_level() {
+    let book = book(&[
+        snapshot(0, &[(42000.0, 1.0), (41990.0, 2.0)], &[(42010.0, 0.5)]),
+        update(1, &[(42000.0, 0.0)], &[(42010.0, 0.0)]),
+    ]);
+
+    assert_eq!(book.bids().collect::<Vec<_>>(), levels(&[(41990.0, 2.0)]));
+    assert_eq!(book.asks().count(), 0);
+    assert_eq!(book.spread(), None);
+
+    // removing every level leaves an empty book
+    let mut book = book;
+    book.apply(&update(2, &[(41990.0, 0.0)], &[]));
+    assert!(book.is_empty());
+}
+
+#[test]
+fn best_levels_spread_and_depth() {
+    let book = book(&[snapshot(
+        0,
+        &[(41990.0, 2.0), (42000.0, 1.0), (41980.0, 4.0)],
+        &[(42030.0, 3.0), (42010.0, 0.5), (42020.0, 1.5)],
+    )]);
+
+    assert_eq!(prices(book.bids()), [42000.0, 41990.0, 41980.0]);
+    assert_eq!(prices(book.asks()), [42010.0, 42020.0, 42030.0]);
+    assert_eq!(
+        book.best_bid(),
+        Some(OrderbookEntry {
+            price: 42000.0,
+            size: 1.0
+        })
+    );
+    assert_eq!(
+        book.best_ask(),
+        Some(OrderbookEntry {
+            price: 42010.0,
+            size: 0.5
+        })
+    );
+    assert_eq!(book.spread(), Some(10.0));
+    assert_eq!(book.mid_price(), Some(42005.0));
+    assert_eq!(book.depth(2), (3.0, 2.0));
+    assert_eq!(book.depth(10), (7.0, 5.0));
+}
+
+#[test]
+fn empty_book() {
+    let book = OrderBook::default();
+
+    assert!(book.is_empty());
+    assert_eq!(book.best_bid(), None);
+    assert_eq!(book.mid_price(), None);
+    assert_eq!(book.depth(5), (0.0, 0.0));
+    assert_eq!(book.timestamp(), None);
+}
+
+#[test]
+fn from_latest_orderbook() {
+    let latest = CryptoOrderbook {
+        timestamp: at(0),
+        bids: levels(&[(41990.0, 2.0), (42000.0, 1.0)]),
+        asks: levels(&[(42010.0, 0.5)]),
+    };
+
+    let mut book = OrderBook::from(&latest);
+
+    assert_eq!(prices(book.bids()), [42000.0, 41990.0]);
+    assert_eq!(prices(book.asks()), [42010.0]);
+    assert_eq!(book.timestamp(), Some(at(0)));
+
+    // the stream updates apply on top of it
+    book.apply(&update(1, &[], &[(42005.0, 0.2)]));
+    assert_eq!(book.spread(), Some(5.0));
+}
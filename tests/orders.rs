@@ -0,0 +1,338 @@
+mod common;
+
+use alpaca_rs::api::trading::CreateOrder;
+use alpaca_rs::prelude::*;
+use common::{num, trading_client, MockResponse, MockServer};
+
+/// Parses a recorded order, and checks that it survives serializing and deserializing again.
+fn parse(json: &str) -> Order {
+    let order: Order = serde_json::from_str(json).unwrap();
+
+    let serialized = serde_json::to_string(&order).unwrap();
+    let reparsed: Order = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(reparsed, order, "serialized as {serialized}");
+
+    order
+}
+
+#[test]
+fn market_order() {
+    let order = parse(include_str!("fixtures/orders/market.json"));
+
+    assert_eq!(order.symbol, "AAPL");
+    assert_eq!(order.order_class, OrderClass::Simple);
+    assert_eq!(order.kind, OrderType::Market);
+    assert_eq!(order.side, OrderSide::Buy);
+    assert_eq!(order.time_in_force, OrderTif::Day);
+    assert_eq!(order.status, OrderStatus::Accepted);
+    assert_eq!(order.qty, Some(num("0.5")));
+    assert_eq!(order.notional, None);
+    assert_eq!(order.filled_qty, num("0"));
+    assert_eq!(order.filled_avg_price, None);
+    assert_eq!(order.filled_at, None);
+    assert!(order.legs.is_empty());
+}
+
+#[test]
+fn filled_limit_order() {
+    let order = parse(include_str!("fixtures/orders/filled_limit.json"));
+
+    assert_eq!(order.client_order_id, "my-limit-order");
+    assert_eq!(
+        order.kind,
+        OrderType::Limit {
+            limit_price: num("107.3")
+        }
+    );
+    assert_eq!(order.status, OrderStatus::Filled);
+    assert_eq!(order.time_in_force, OrderTif::GoodTillCanceled);
+    assert_eq!(order.qty, Some(num("10")));
+    assert_eq!(order.filled_qty, num("10"));
+    assert_eq!(order.filled_avg_price, Some(num("107.32")));
+    assert_eq!(
+        order.filled_at.unwrap().to_rfc3339(),
+        "2021-03-16T14:30:02.650001+00:00"
+    );
+    assert!(order.extended_hours);
+}
+
+#[test]
+fn trailing_stop_order() {
+    let order = parse(include_str!("fixtures/orders/trailing_stop.json"));
+
+    assert_eq!(
+        order.kind,
+        OrderType::TrailingStop(TrailingStop::Percent {
+            trail_percent: num("5")
+        })
+    );
+    assert_eq!(order.hwm, Some(num("150.25")));
+    assert_eq!(order.order_class, OrderClass::Simple);
+    assert_eq!(order.status, OrderStatus::New);
+}
+
+#[test]
+fn nested_bracket_order() {
+    let order = parse(include_str!("fixtures/orders/bracket.json"));
+
+    assert_eq!(order.order_class, OrderClass::Bracket);
+    assert_eq!(
+        order.kind,
+        OrderType::Limit {
+            limit_price: num("120.1")
+        }
+    );
+    assert_eq!(order.filled_avg_price, Some(num("120.05")));
+
+    let [take_profit, stop_loss] = order.legs.as_slice() else {
+        panic!("expected two legs, got {:?}", order.legs);
+    };
+    assert_eq!(take_profit.client_order_id, "bracket-take-profit");
+    assert_eq!(take_profit.side, OrderSide::Sell);
+    assert_eq!(
+        take_profit.kind,
+        OrderType::Limit {
+            limit_price: num("130")
+        }
+    );
+    assert_eq!(take_profit.status, OrderStatus::New);
+    assert_eq!(stop_loss.client_order_id, "bracket-stop-loss");
+    assert_eq!(
+        stop_loss.kind,
+        OrderType::Stop {
+            stop_price: num("115.5")
+        }
+    );
+    assert_eq!(stop_loss.status, OrderStatus::Held);
+    assert!(stop_loss.legs.is_empty());
+}
+
+fn take_profit(limit_price: &str) -> TakeProfit {
+    TakeProfit {
+        limit_price: num(limit_price),
+    }
+}
+
+fn stop_loss(stop_price: &str) -> StopLoss {
+    StopLoss {
+        stop_price: num(stop_price),
+        limit_price: None,
+    }
+}
+
+fn buy_bracket() -> CreateOrder {
+    CreateOrder::bracket(
+        "AAPL",
+        num("10"),
+        OrderSide::Buy,
+        take_profit("130"),
+        stop_loss("115"),
+    )
+    .unwrap()
+}
+
+fn sell_oco() -> CreateOrder {
+    CreateOrder::one_cancels_other(
+        "AAPL",
+        num("10"),
+        OrderSide::Sell,
+        take_profit("130"),
+        stop_loss("115"),
+    )
+    .unwrap()
+}
+
+fn oto() -> CreateOrder {
+    CreateOrder::one_triggers_other(
+        "AAPL",
+        num("10"),
+        OrderSide::Buy,
+        Some(take_profit("130")),
+        None,
+    )
+    .unwrap()
+}
+
+fn option_leg() -> OrderLeg {
+    OrderLeg {
+        symbol: "AAPL240119C00150000".into(),
+        ratio_qty: 1,
+        side: OrderSide::Buy,
+        position_intent: Some(PositionIntent::BuyToOpen),
+    }
+}
+
+#[test]
+fn valid_orders() {
+    let cases = [
+        ("buy bracket", buy_bracket()),
+        (
+            "sell bracket",
+            CreateOrder {
+                side: OrderSide::Sell,
+                take_profit: Some(take_profit("110")),
+                stop_loss: Some(stop_loss("120")),
+                ..buy_bracket()
+            },
+        ),
+        ("sell oco", sell_oco()),
+        (
+            "buy oco",
+            CreateOrder {
+                side: OrderSide::Buy,
+                kind: OrderType::Limit {
+                    limit_price: num("110"),
+                },
+                take_profit: Some(take_profit("110")),
+                stop_loss: Some(stop_loss("120")),
+                ..sell_oco()
+            },
+        ),
+        ("oto with a take-profit", oto()),
+        (
+            "oto with a stop-loss",
+            CreateOrder {
+                take_profit: None,
+                stop_loss: Some(stop_loss("115")),
+                ..oto()
+            },
+        ),
+        (
+            "multi-leg",
+            CreateOrder::multi_leg(num("1"), OrderType::Market, vec![option_leg()]).unwrap(),
+        ),
+    ];
+
+    for (name, order) in cases {
+        assert!(order.validate().is_ok(), "{name}: {:?}", order.validate());
+    }
+}
+
+#[test]
+fn rejected_orders() {
+    let cases = [
+        (
+            "bracket with IOC",
+            CreateOrder {
+                time_in_force: OrderTif::ImmediateOrCancel,
+                ..buy_bracket()
+            },
+            "advanced orders must be either day or good till canceled orders",
+        ),
+        (
+            "bracket with FOK",
+            CreateOrder {
+                time_in_force: OrderTif::FillOrKill,
+                ..buy_bracket()
+            },
+            "advanced orders must be either day or good till canceled orders",
+        ),
+        (
+            "bracket in extended hours",
+            CreateOrder {
+                extended_hours: true,
+                ..buy_bracket()
+            },
+            "advanced orders cannot be processed in extended hours",
+        ),
+        (
+            "bracket with a notional amount",
+            CreateOrder {
+                amount: OrderAmount::Notional(num("1000")),
+                ..buy_bracket()
+            },
+            "advanced orders must be placed with a quantity, not a notional amount",
+        ),
+        (
+            "buy bracket with the take-profit below the stop-loss",
+            CreateOrder {
+                take_profit: Some(take_profit("110")),
+                ..buy_bracket()
+            },
+            "the take-profit limit price is on the wrong side of the stop-loss stop price",
+        ),
+        (
+            "sell oco with the take-profit below the stop-loss",
+            CreateOrder {
+                kind: OrderType::Limit {
+                    limit_price: num("110"),
+                },
+                take_profit: Some(take_profit("110")),
+                ..sell_oco()
+            },
+            "the take-profit limit price is on the wrong side of the stop-loss stop price",
+        ),
+        (
+            "oto with both legs",
+            CreateOrder {
+                stop_loss: Some(stop_loss("115")),
+                ..oto()
+            },
+            "one-triggers-other orders need exactly one of a take-profit or a stop-loss leg",
+        ),
+        (
+            "oto without legs",
+            CreateOrder {
+                take_profit: None,
+                ..oto()
+            },
+            "one-triggers-other orders need exactly one of a take-profit or a stop-loss leg",
+        ),
+        (
+            "simple order with take-profit and stop-loss legs",
+            CreateOrder {
+                order_class: OrderClass::Simple,
+                ..buy_bracket()
+            },
+            "simple orders cannot have take-profit or stop-loss legs",
+        ),
+        (
+            "simple order with multi-leg legs",
+            CreateOrder {
+                order_class: OrderClass::Simple,
+                take_profit: None,
+                stop_loss: None,
+                legs: Some(vec![option_leg()]),
+                ..buy_bracket()
+            },
+            "only multi-leg orders can have legs",
+        ),
+    ];
+
+    for (name, order, expected) in cases {
+        match order.validate() {
+            Err(AlpacaError::InvalidOrder(message)) => assert_eq!(message, expected, "{name}"),
+            other => panic!("{name}: expected an invalid order, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn constructors_validate() {
+    assert!(CreateOrder::bracket(
+        "AAPL",
+        num("10"),
+        OrderSide::Buy,
+        take_profit("110"),
+        stop_loss("115"),
+    )
+    .is_err());
+    assert!(
+        CreateOrder::one_triggers_other("AAPL", num("10"), OrderSide::Buy, None, None).is_err()
+    );
+    assert!(CreateOrder::multi_leg(num("1"), OrderType::Market, Vec::new()).is_err());
+}
+
+#[async_std::test]
+async fn invalid_orders_are_not_sent() {
+    let server = MockServer::start(vec![MockResponse::json("{}")]).await;
+    let client = trading_client(&server);
+    let order = CreateOrder {
+        extended_hours: true,
+        ..buy_bracket()
+    };
+
+    assert!(serde_json::to_string(&order).is_err());
+    assert!(client.execute(order).await.is_err());
+    assert!(server.requests().is_empty());
+}
@@ -0,0 +1,221 @@
+//! Paginates recorded multi-symbol responses of the Market Data API, served as two pages with a
+//! `next_page_token` and a last page with a `null` one.
+mod common;
+
+use alpaca_rs::api::market_data::{
+    GetHistoricalAuctions, GetHistoricalQuotes, GetHistoricalTrades, StockFeed,
+};
+use alpaca_rs::pagination::PaginationEndpoint;
+use common::{market_data_client, MockResponse, MockServer};
+use futures::StreamExt;
+use std::time::Duration;
+
+/// Three pages of a recorded response, whose tokens are `p1`, `p2` and `null`.
+fn pages(recorded: &str) -> Vec<MockResponse> {
+    let mut page: serde_json::Value = serde_json::from_str(recorded).unwrap();
+    [Some("p1"), Some("p2"), None]
+        .into_iter()
+        .map(|token| {
+            page["next_page_token"] = token.into();
+            MockResponse::json(page.to_string())
+        })
+        .collect()
+}
+
+/// Checks that the pages were requested from `path`, each with the token of the previous page.
+fn assert_requested_pages(server: &MockServer, path: &str) {
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    for (request, token) in requests.iter().zip([None, Some("p1"), Some("p2")]) {
+        assert_eq!(request.path(), path);
+        assert_eq!(request.query("symbols").as_deref(), Some("AAPL,MSFT"));
+        assert_eq!(request.query("limit").as_deref(), Some("2"));
+        assert_eq!(request.query("page_token").as_deref(), token);
+    }
+}
+
+fn symbols() -> Vec<String> {
+    vec!["AAPL".into(), "MSFT".into()]
+}
+
+fn get_trades() -> GetHistoricalTrades {
+    GetHistoricalTrades {
+        symbols: symbols(),
+        start: None,
+        end: None,
+        limit: None,
+        asof: None,
+        feed: None,
+        currency: None,
+        sort: None,
+        page_token: None,
+    }
+}
+
+#[async_std::test]
+async fn historical_trades() {
+    let server = MockServer::start(pages(include_str!("fixtures/stocks/trades.json"))).await;
+    let client = market_data_client(&server);
+    let mut pagination = get_trades().paginate(2, &client);
+
+    for token in [Some("p1"), Some("p2"), None] {
+        let page = pagination.next().await.unwrap().unwrap();
+        assert_eq!(page.next_page_token.as_deref(), token);
+        assert_eq!(page.trades["AAPL"].len(), 2);
+        assert_eq!(page.trades["AAPL"][0].price, 184.22);
+        assert_eq!(page.trades["MSFT"].len(), 1);
+        assert_eq!(page.trades["MSFT"][0].trade_id, 52983525033527);
+    }
+    assert!(pagination.is_exhausted());
+    assert!(pagination.next().await.unwrap().is_none());
+
+    assert_requested_pages(&server, "/v2/stocks/trades");
+}
+
+#[async_std::test]
+async fn historical_quotes() {
+    let server = MockServer::start(pages(include_str!("fixtures/stocks/quotes.json"))).await;
+    let client = market_data_client(&server);
+    let mut pagination = GetHistoricalQuotes {
+        symbols: symbols(),
+        start: None,
+        end: None,
+        limit: None,
+        asof: None,
+        feed: StockFeed::InvestorsExchange,
+        sort: None,
+        page_token: None,
+    }
+    .paginate(2, &client);
+
+    for token in [Some("p1"), Some("p2"), None] {
+        let page = pagination.next().await.unwrap().unwrap();
+        assert_eq!(page.next_page_token.as_deref(), token);
+        assert_eq!(page.quotes["AAPL"].len(), 1);
+        assert_eq!(page.quotes["MSFT"].len(), 2);
+        assert_eq!(page.quotes["MSFT"][1].bid_size, 2.0);
+    }
+    assert!(pagination.is_exhausted());
+    assert!(pagination.next().await.unwrap().is_none());
+
+    assert_requested_pages(&server, "/v2/stocks/quotes");
+}
+
+#[async_std::test]
+async fn historical_auctions() {
+    let server = MockServer::start(pages(include_str!("fixtures/stocks/auctions.json"))).await;
+    let client = market_data_client(&server);
+    let mut pagination = GetHistoricalAuctions {
+        symbols: symbols(),
+        start: None,
+        end: None,
+        limit: None,
+        asof: None,
+        feed: StockFeed::Sip,
+        currency: None,
+        sort: None,
+        page_token: None,
+    }
+    .paginate(2, &client);
+
+    for token in [Some("p1"), Some("p2"), None] {
+        let page = pagination.next().await.unwrap().unwrap();
+        assert_eq!(page.next_page_token.as_deref(), token);
+        let [aapl] = page.auctions["AAPL"].as_slice() else {
+            panic!("expected one day of AAPL auctions, got {:?}", page.auctions);
+        };
+        assert_eq!(aapl.opening.len(), 2);
+        assert_eq!(aapl.closing[0].price, 184.25);
+        assert_eq!(page.auctions["MSFT"].len(), 1);
+    }
+    assert!(pagination.is_exhausted());
+    assert!(pagination.next().await.unwrap().is_none());
+
+    assert_requested_pages(&server, "/v2/stocks/auctions");
+}
+
+// every page of trades has three items, two of AAPL and one of MSFT
+
+#[async_std::test]
+async fn stream_ends_on_the_last_page() {
+    let server = MockServer::start(pages(include_str!("fixtures/stocks/trades.json"))).await;
+    let client = market_data_client(&server);
+
+    let trades: Vec<_> = get_trades()
+        .paginate(2, &client)
+        .into_stream()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    assert_eq!(trades.len(), 9);
+    let aapl = trades.iter().filter(|(symbol, _)| symbol == "AAPL").count();
+    assert_eq!(aapl, 6);
+    assert!(trades
+        .iter()
+        .any(|(symbol, trade)| symbol == "MSFT" && trade.trade_id == 52983525033527));
+    assert_requested_pages(&server, "/v2/stocks/trades");
+}
+
+#[async_std::test]
+async fn stream_stops_at_max_items() {
+    let server = MockServer::start(pages(include_str!("fixtures/stocks/trades.json"))).await;
+    let client = market_data_client(&server);
+
+    let trades: Vec<_> = get_trades()
+        .paginate(2, &client)
+        .into_stream()
+        .max_items(3)
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    assert_eq!(trades.len(), 3);
+    assert_eq!(server.requests().len(), 1);
+}
+
+/// Takes the items of the first page one at a time, giving the stream time in between to make
+/// progress on a page it fetches ahead, and returns how many pages were requested by then.
+async fn requests_during_first_page(prefetch: bool) -> usize {
+    let server = MockServer::start(pages(include_str!("fixtures/stocks/trades.json"))).await;
+    let client = market_data_client(&server);
+    let mut trades = get_trades()
+        .paginate(2, &client)
+        .into_stream()
+        .prefetch(prefetch);
+
+    for _ in 0..3 {
+        trades.next().await.unwrap().unwrap();
+        async_std::task::sleep(Duration::from_millis(50)).await;
+    }
+
+    server.requests().len()
+}
+
+#[async_std::test]
+async fn stream_prefetches_one_page_ahead() {
+    assert_eq!(requests_during_first_page(false).await, 1);
+    assert_eq!(requests_during_first_page(true).await, 2);
+}
+
+#[async_std::test]
+async fn stream_yields_the_items_before_a_failed_page() {
+    let mut responses = pages(include_str!("fixtures/stocks/trades.json"));
+    responses[2] = MockResponse::status(500);
+    let server = MockServer::start(responses).await;
+    let client = market_data_client(&server);
+    let trades = get_trades()
+        .paginate(2, &client)
+        .into_stream()
+        .prefetch(true);
+    assert_send(&trades);
+
+    let results: Vec<_> = trades.collect().await;
+
+    assert_eq!(results.len(), 7);
+    assert!(results[..6].iter().all(Result::is_ok));
+    assert!(results[6].is_err());
+    assert_eq!(server.requests().len(), 3);
+}
+
+fn assert_send<T: Send>(_: &T) {}
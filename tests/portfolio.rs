@@ -0,0 +1,83 @@
+//! Recorded portfolio histories. Daily points are at midnight New York time, which is 5:00 UTC in
+//! the winter and 4:00 UTC in the summer.
+use alpaca_rs::chrono::NaiveDate;
+use alpaca_rs::prelude::*;
+
+fn history(json: &str) -> PortfolioHistory {
+    serde_json::from_str(json).unwrap()
+}
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+fn assert_returns(actual: Vec<(NaiveDate, f64)>, expected: &[(NaiveDate, f64)]) {
+    assert_eq!(actual.len(), expected.len(), "{actual:?}");
+    for ((date, r), (expected_date, expected_r)) in actual.iter().zip(expected) {
+        assert_eq!(date, expected_date, "{actual:?}");
+        assert!((r - expected_r).abs() < 1e-9, "{date}: {r} != {expected_r}");
+    }
+}
+
+#[test]
+fn winter() {
+    let history = history(include_str!("fixtures/portfolio/winter.json"));
+
+    assert!((history.max_drawdown() - 0.1).abs() < 1e-9);
+    // the first point has no equity, the second has no previous day
+    assert_returns(
+        history.daily_returns(),
+        &[
+            (date(2024, 1, 3), 0.05),
+            (date(2024, 1, 4), -0.1),
+            (date(2024, 1, 5), 0.05),
+        ],
+    );
+}
+
+#[test]
+fn summer() {
+    let history = history(include_str!("fixtures/portfolio/summer.json"));
+
+    assert!((history.max_drawdown() - 0.1).abs() < 1e-9);
+    assert_returns(
+        history.daily_returns(),
+        &[
+            (date(2024, 7, 2), 0.02),
+            (date(2024, 7, 3), -0.05),
+            (date(2024, 7, 5), 18360.0 / 19380.0 - 1.0),
+            (date(2024, 7, 8), 0.05),
+        ],
+    );
+}
+
+#[test]
+fn winter_continuous_intraday() {
+    let history = history(include_str!("fixtures/portfolio/continuous.json"));
+
+    assert!((history.max_drawdown() - 0.1).abs() < 1e-9);
+    // the points at 23:00 New York time close their day, 4:00 UTC of the next day
+    assert_returns(
+        history.daily_returns(),
+        &[(date(2024, 1, 3), 0.1), (date(2024, 1, 4), -0.1)],
+    );
+}
+
+#[test]
+fn day_without_equity() {
+    let mut history = history(include_str!("fixtures/portfolio/winter.json"));
+    history.equity = vec![None, Some(0.0), Some(100.0), Some(110.0), Some(99.0)];
+
+    assert_returns(
+        history.daily_returns(),
+        &[(date(2024, 1, 4), 0.1), (date(2024, 1, 5), -0.1)],
+    );
+}
+
+#[test]
+fn no_drawdown() {
+    let mut history = history(include_str!("fixtures/portfolio/winter.json"));
+    history.equity = vec![None, Some(100.0), Some(100.0), Some(110.0), Some(120.0)];
+
+    assert_eq!(history.max_drawdown(), 0.0);
+}
@@ -0,0 +1,123 @@
+//! Quantities are fractional, and sent as strings like `"0.5"` by the Trading API. These tests
+//! run with both number types, `cargo test` and `cargo test --features decimal`.
+mod common;
+
+use alpaca_rs::api::trading::ClosePosition;
+use alpaca_rs::prelude::*;
+use common::{num, trading_client, MockResponse, MockServer};
+use serde_json::json;
+
+#[test]
+fn order_amount() {
+    for qty in ["0.5", "10", "0.000000001"] {
+        let amount: OrderAmount = serde_json::from_value(json!({ "qty": qty })).unwrap();
+        assert_eq!(amount, OrderAmount::Quantity(num(qty)));
+        assert_eq!(
+            serde_json::to_value(&amount).unwrap(),
+            json!({ "qty": qty })
+        );
+    }
+
+    let amount: OrderAmount = serde_json::from_value(json!({ "notional": "25.5" })).unwrap();
+    assert_eq!(amount, OrderAmount::Notional(num("25.5")));
+}
+
+fn position(qty: &str) -> serde_json::Value {
+    json!({
+        "asset_id": "b0b6dd9d-8b9b-48a9-ba46-b9d54906e415",
+        "symbol": "AAPL",
+        "exchange": "NASDAQ",
+        "asset_class": "us_equity",
+        "asset_marginable": true,
+        "avg_entry_price": "120.5",
+        "qty": qty,
+        "side": "long",
+        "market_value": "60.5",
+        "cost_basis": "60.25",
+        "unrealized_pl": "0.25",
+        "unrealized_plpc": "0.0041493775933610",
+        "unrealized_intraday_pl": "0.25",
+        "unrealized_intraday_plpc": "0.0041493775933610",
+        "current_price": "121",
+        "lastday_price": "120",
+        "change_today": "0.0083333333333333",
+        "qty_available": qty
+    })
+}
+
+#[test]
+fn open_position_qty() {
+    let fractional: OpenPosition = serde_json::from_value(position("0.5")).unwrap();
+    assert_eq!(fractional.qty, num("0.5"));
+    assert_eq!(fractional.avg_entry_price, num("120.5"));
+
+    let whole: OpenPosition = serde_json::from_value(position("100")).unwrap();
+    assert_eq!(whole.qty, num("100"));
+
+    let reparsed: OpenPosition =
+        serde_json::from_value(serde_json::to_value(&fractional).unwrap()).unwrap();
+    assert_eq!(reparsed, fractional);
+}
+
+#[test]
+fn market_data_sizes() {
+    let bar: HistoricalBar = serde_json::from_value(json!({
+        "t": "2024-01-03T14:30:00Z",
+        "o": 42000.5,
+        "h": 42100,
+        "l": 41900,
+        "c": 42050.25,
+        "v": 0.5,
+        "n": 3,
+        "vw": 42010.1
+    }))
+    .unwrap();
+    assert_eq!(bar.volume, 0.5);
+
+    let trade: HistoricalTrade = serde_json::from_value(json!({
+        "t": "2024-01-03T14:30:00.123Z",
+        "x": "V",
+        "p": 185.5,
+        "s": 100,
+        "i": 52983525029461_i64,
+        "c": ["@"],
+        "z": "C"
+    }))
+    .unwrap();
+    assert_eq!(trade.size, 100.0);
+
+    let quote: Quote = serde_json::from_value(json!({
+        "t": "2024-01-03T14:30:00.123Z",
+        "bx": "V",
+        "bp": 185.49,
+        "bs": 0.25,
+        "ax": "V",
+        "ap": 185.51,
+        "as": 3,
+        "c": ["R"],
+        "z": "C"
+    }))
+    .unwrap();
+    assert_eq!(quote.bid_size, 0.25);
+    assert_eq!(quote.ask_size, 3.0);
+}
+
+#[async_std::test]
+async fn close_position_qty() {
+    let server = MockServer::start(vec![MockResponse::json("{}")]).await;
+    let client = trading_client(&server);
+
+    for qty in ["0.5", "3"] {
+        let _ = client
+            .execute(ClosePosition {
+                symbol_or_asset_id: SymbolOrAssetId::SymbolId("AAPL".into()),
+                qty: Some(num(qty)),
+                percentage: None,
+            })
+            .await;
+    }
+
+    let requests = server.requests();
+    assert_eq!(requests[0].query("qty").as_deref(), Some("0.5"));
+    assert_eq!(requests[1].query("qty").as_deref(), Some("3"));
+}
@@ -0,0 +1,141 @@
+mod common;
+
+use alpaca_rs::api::trading::GetClock;
+use alpaca_rs::chrono::Utc;
+use alpaca_rs::prelude::*;
+use common::{auth, MockResponse, MockServer};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const CLOCK: &str = r#"{
+    "timestamp": "2024-01-03T10:00:00-05:00",
+    "is_open": true,
+    "next_open": "2024-01-04T09:30:00-05:00",
+    "next_close": "2024-01-03T16:00:00-05:00"
+}"#;
+
+fn client(server: &MockServer, rate_limit: RateLimit) -> TradingClient {
+    TradingClient::new(auth(), server.url("/v2/").parse().unwrap())
+        .with_rate_limit(rate_limit)
+        .with_retry_policy(RetryPolicy::none())
+}
+
+/// A rate limit reset `seconds` from now, as sent in `X-RateLimit-Reset`, and as a time.
+fn reset_in(seconds: i64) -> (i64, SystemTime) {
+    let reset = Utc::now().timestamp() + seconds;
+    (
+        reset,
+        UNIX_EPOCH + Duration::from_secs(reset.try_into().unwrap()),
+    )
+}
+
+#[async_std::test]
+async fn throttles_at_the_reserve() {
+    let (reset, reset_at) = reset_in(2);
+    let server = MockServer::start(vec![
+        MockResponse::json(CLOCK)
+            .header("X-RateLimit-Remaining", 5)
+            .header("X-RateLimit-Reset", reset),
+        MockResponse::json(CLOCK),
+    ])
+    .await;
+    let client = client(
+        &server,
+        RateLimit {
+            reserve: 5,
+            ..Default::default()
+        },
+    );
+
+    client.execute(GetClock).await.unwrap();
+    client.execute(GetClock).await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(
+        requests[1].at >= reset_at,
+        "the second request was sent before the rate limit reset"
+    );
+}
+
+#[async_std::test]
+async fn does_not_throttle_above_the_reserve() {
+    let (reset, reset_at) = reset_in(60);
+    let server = MockServer::start(vec![MockResponse::json(CLOCK)
+        .header("X-RateLimit-Remaining", 100)
+        .header("X-RateLimit-Reset", reset)])
+    .await;
+    let client = client(&server, RateLimit::default());
+
+    client.execute(GetClock).await.unwrap();
+    client.execute(GetClock).await.unwrap();
+
+    assert!(server.requests()[1].at < reset_at);
+}
+
+#[async_std::test]
+async fn retries_after_retry_after() {
+    let server = MockServer::start(vec![
+        MockResponse::error(429, 42910000, "rate limit exceeded").header("Retry-After", 1),
+        MockResponse::json(CLOCK),
+    ])
+    .await;
+    let client = client(&server, RateLimit::default());
+
+    client.execute(GetClock).await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    let waited = requests[1].at.duration_since(requests[0].at).unwrap();
+    assert!(waited >= Duration::from_millis(900), "waited {waited:?}");
+}
+
+#[async_std::test]
+async fn retries_after_the_rate_limit_reset() {
+    let (reset, reset_at) = reset_in(1);
+    let server = MockServer::start(vec![
+        MockResponse::error(429, 42910000, "rate limit exceeded")
+            .header("X-RateLimit-Remaining", 0)
+            .header("X-RateLimit-Reset", reset),
+        MockResponse::json(CLOCK),
+    ])
+    .await;
+    let client = client(
+        &server,
+        RateLimit {
+            throttle: false,
+            ..Default::default()
+        },
+    );
+
+    client.execute(GetClock).await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[1].at >= reset_at);
+}
+
+#[async_std::test]
+async fn gives_up_after_max_retries() {
+    let server = MockServer::start(vec![MockResponse::error(
+        429,
+        42910000,
+        "rate limit exceeded",
+    )])
+    .await;
+    let client = client(
+        &server,
+        RateLimit {
+            throttle: false,
+            max_retries: 2,
+            fallback_delay: Duration::from_millis(10),
+            ..Default::default()
+        },
+    );
+
+    let error = client.execute(GetClock).await.unwrap_err();
+
+    assert!(matches!(error, AlpacaError::Api { .. }), "{error:?}");
+    assert!(error.is_rate_limited());
+    assert_eq!(error.code(), Some(42910000));
+    assert_eq!(server.requests().len(), 3);
+}
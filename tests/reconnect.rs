@@ -0,0 +1,124 @@
+//! Drops the connection of a [`ReconnectingClient`] to a local stand-in for the real-time stock
+//! data stream.
+#![cfg(feature = "market-data-live")]
+mod common;
+
+use alpaca_rs::api::market_data::live::{Encoding, LiveEvent, ReconnectingClient};
+use alpaca_rs::chrono::Utc;
+use alpaca_rs::prelude::*;
+use common::{auth, stream::MockStream};
+use futures::StreamExt;
+use serde_json::json;
+use std::time::Duration;
+
+async fn connect(stream: &MockStream) -> ReconnectingClient {
+    ReconnectingClient::connect(auth(), stream.url().parse().unwrap(), Encoding::Json)
+        .await
+        .unwrap()
+        .with_backoff(RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(10),
+            jitter: false,
+        })
+}
+
+async fn next(client: &mut ReconnectingClient) -> LiveEvent {
+    client.next().await.unwrap().unwrap()
+}
+
+fn aapl_trades() -> StockSubscription {
+    StockSubscription {
+        trades: vec!["AAPL".into()],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn clients_are_send() {
+    fn assert_send<T: Send>() {}
+
+    assert_send::<ReconnectingClient>();
+    assert_send::<ReconnectingClient<CryptoStreamMessage>>();
+}
+
+#[async_std::test]
+async fn reconnects_and_replays_the_subscription() {
+    let stream = MockStream::start().await;
+    let mut client = connect(&stream).await;
+    client.subscribe(&aapl_trades()).await.unwrap();
+    let LiveEvent::Message(StockStreamMessage::Subscription(_)) = next(&mut client).await else {
+        panic!("expected a subscription confirmation");
+    };
+
+    stream
+        .send(json!([{
+            "T": "t",
+            "S": "AAPL",
+            "i": 52983525029461_i64,
+            "x": "V",
+            "p": 184.22,
+            "s": 100,
+            "c": ["@"],
+            "t": "2024-01-03T14:30:00.004285Z",
+            "z": "C"
+        }]))
+        .await;
+    let LiveEvent::Message(StockStreamMessage::Trade(trade)) = next(&mut client).await else {
+        panic!("expected a trade");
+    };
+
+    let dropped = Utc::now();
+    stream.disconnect().await;
+
+    let LiveEvent::Reconnected(gap) = next(&mut client).await else {
+        panic!("expected to reconnect");
+    };
+    // the gap starts at the exchange time of the last trade, not when it was received
+    assert_eq!(gap.from, trade.data.timestamp);
+    assert!(dropped <= gap.to, "{gap:?}");
+
+    let replayed = stream.actions(2).await;
+    assert_eq!(stream.connections().len(), 2);
+    assert_eq!(
+        replayed[0],
+        json!({ "action": "auth", "key": "key", "secret": "secret" })
+    );
+    assert_eq!(replayed[1]["action"], "subscribe");
+    assert_eq!(replayed[1]["trades"], json!(["AAPL"]));
+
+    let LiveEvent::Message(StockStreamMessage::Subscription(confirmed)) = next(&mut client).await
+    else {
+        panic!("expected the replayed subscription to be confirmed");
+    };
+    assert_eq!(confirmed, aapl_trades());
+    assert_eq!(client.subscription(), aapl_trades());
+}
+
+#[async_std::test]
+async fn subscribes_while_reconnecting() {
+    let stream = MockStream::start().await;
+    let mut client = connect(&stream).await;
+    client.subscribe(&aapl_trades()).await.unwrap();
+    next(&mut client).await;
+
+    stream.disconnect().await;
+    // the lost connection is only noticed while polling, so this goes out on the old connection
+    // or is replayed, either way it must end up on the new one
+    let msft_quotes = StockSubscription {
+        quotes: vec!["MSFT".into()],
+        ..Default::default()
+    };
+    let _ = client.subscribe(&msft_quotes).await;
+
+    loop {
+        if let LiveEvent::Reconnected(_) = next(&mut client).await {
+            break;
+        }
+    }
+
+    let replayed = stream.actions(2).await;
+    assert_eq!(replayed[1]["action"], "subscribe");
+    assert_eq!(replayed[1]["trades"], json!(["AAPL"]));
+    assert_eq!(replayed[1]["quotes"], json!(["MSFT"]));
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use alpaca_rs::api::trading::{CreateOrder, GetClock};
+use alpaca_rs::prelude::*;
+use common::{auth, MockResponse, MockServer};
+use std::time::Duration;
+
+fn client(server: &MockServer) -> TradingClient {
+    TradingClient::new(auth(), server.url("/v2/").parse().unwrap())
+        .with_rate_limit(RateLimit::disabled())
+        .with_retry_policy(RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(10),
+            jitter: false,
+        })
+}
+
+fn order(client_order_id: Option<&str>) -> CreateOrder {
+    let mut order = CreateOrder::bracket(
+        "AAPL",
+        "10".parse().unwrap(),
+        OrderSide::Buy,
+        TakeProfit {
+            limit_price: "130".parse().unwrap(),
+        },
+        StopLoss {
+            stop_price: "115".parse().unwrap(),
+            limit_price: None,
+        },
+    )
+    .unwrap();
+    order.client_order_id = client_order_id.map(Into::into);
+    order
+}
+
+fn is_service_unavailable(error: &AlpacaError) -> bool {
+    error.status().map(|status| status as u16) == Some(503)
+}
+
+#[async_std::test]
+async fn retries_get_requests() {
+    let server = MockServer::start(vec![MockResponse::status(503)]).await;
+
+    let error = client(&server).execute(GetClock).await.unwrap_err();
+
+    assert!(is_service_unavailable(&error), "{error:?}");
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[async_std::test]
+async fn does_not_retry_a_post_without_client_order_id() {
+    let server = MockServer::start(vec![MockResponse::status(503)]).await;
+
+    let error = client(&server).execute(order(None)).await.unwrap_err();
+
+    assert!(is_service_unavailable(&error), "{error:?}");
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[async_std::test]
+async fn retries_a_post_with_client_order_id() {
+    let server = MockServer::start(vec![MockResponse::status(503)]).await;
+
+    let error = client(&server)
+        .execute(order(Some("my-order")))
+        .await
+        .unwrap_err();
+
+    assert!(is_service_unavailable(&error), "{error:?}");
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    // every attempt carries the same order
+    assert!(requests
+        .iter()
+        .all(|request| request.json()["client_order_id"] == "my-order"));
+}
@@ -0,0 +1,91 @@
+//! Runs [`TradeUpdates`] against a local stand-in for the trade updates stream.
+#![cfg(feature = "trading-live")]
+mod common;
+
+use alpaca_rs::api::trading::updates::TradeUpdates;
+use alpaca_rs::chrono::{self, TimeZone, Utc};
+use alpaca_rs::prelude::*;
+use common::{auth, num, stream::MockStream};
+use futures::StreamExt;
+use serde_json::json;
+
+async fn connect(stream: &MockStream, auth: &TradingAuth) -> Result<TradeUpdates, AlpacaError> {
+    TradeUpdates::connect(auth, stream.url().parse().unwrap()).await
+}
+
+#[async_std::test]
+async fn authenticates_and_listens() {
+    let stream = MockStream::start_trading().await;
+    connect(&stream, &auth()).await.unwrap();
+
+    let actions = stream.actions(2).await;
+    assert_eq!(
+        actions,
+        [
+            json!({ "action": "auth", "key": "key", "secret": "secret" }),
+            json!({ "action": "listen", "data": { "streams": ["trade_updates"] } }),
+        ]
+    );
+}
+
+#[async_std::test]
+async fn fails_on_rejected_authorization() {
+    let stream = MockStream::start_trading().await;
+    let wrong = TradingAuth {
+        key_id: "key".to_owned(),
+        secret: "wrong".to_owned(),
+    };
+
+    let error = connect(&stream, &wrong).await.err().unwrap();
+
+    assert!(
+        matches!(error, AlpacaError::Stream { code: None, .. }),
+        "{error:?}"
+    );
+    // nothing is listened to without authorization
+    assert_eq!(
+        stream.connections(),
+        [[json!({
+            "action": "auth",
+            "key": "key",
+            "secret": "wrong",
+        })]]
+    );
+}
+
+#[async_std::test]
+async fn decodes_a_fill() {
+    let stream = MockStream::start_trading().await;
+    let mut updates = connect(&stream, &auth()).await.unwrap();
+    let order: serde_json::Value =
+        serde_json::from_str(include_str!("fixtures/orders/filled_limit.json")).unwrap();
+
+    stream
+        .send(json!({
+            "stream": "trade_updates",
+            "data": {
+                "event": "fill",
+                "execution_id": "b1c3a7e2-5c4d-4e8f-9a0b-1c2d3e4f5a6b",
+                "order": order,
+                "timestamp": "2021-03-16T14:30:02.650001Z",
+                "price": "107.32",
+                "qty": "10",
+                "position_qty": "10",
+            },
+        }))
+        .await;
+    let update = updates.next().await.unwrap().unwrap();
+
+    assert_eq!(update.event, TradeEvent::Fill);
+    assert_eq!(update.order.client_order_id, "my-limit-order");
+    assert_eq!(update.price, Some(num("107.32")));
+    assert_eq!(update.qty, Some(num("10")));
+    assert_eq!(update.position_qty, Some(num("10")));
+    assert_eq!(
+        update.timestamp,
+        Some(
+            Utc.with_ymd_and_hms(2021, 3, 16, 14, 30, 2).unwrap()
+                + chrono::Duration::microseconds(650_001)
+        )
+    );
+}
@@ -0,0 +1,248 @@
+//! The endpoint paths are relative to the base url of the Trading API, which includes the API
+//! version (`/v2/`), so every request has to end up under it.
+mod common;
+
+use alpaca_rs::api::trading::*;
+use alpaca_rs::prelude::*;
+use common::{trading_client, MockResponse, MockServer};
+
+fn request(method: &str, path: &str) -> (String, String) {
+    (method.to_owned(), path.to_owned())
+}
+
+#[async_std::test]
+async fn order_paths() {
+    let server = MockServer::start(vec![MockResponse::json("[]")]).await;
+    let client = trading_client(&server);
+
+    let _ = client.execute(GetOrders::default()).await;
+    let _ = client
+        .execute(GetOrder {
+            order_id: "61e69015".into(),
+            nested: None,
+        })
+        .await;
+    let _ = client
+        .execute(GetOrderByClientId {
+            client_order_id: "my-order".into(),
+        })
+        .await;
+    let _ = client
+        .execute(CancelOrder {
+            order_id: "61e69015".into(),
+        })
+        .await;
+    let _ = client.execute(CancelAllOrders).await;
+
+    assert_eq!(
+        server.paths(),
+        [
+            request("GET", "/v2/orders"),
+            request("GET", "/v2/orders/61e69015"),
+            request("GET", "/v2/orders:by_client_order_id"),
+            request("DELETE", "/v2/orders/61e69015"),
+            request("DELETE", "/v2/orders"),
+        ]
+    );
+    assert_eq!(
+        server.requests()[2].query("client_order_id").as_deref(),
+        Some("my-order")
+    );
+}
+
+#[async_std::test]
+async fn position_paths() {
+    let server = MockServer::start(vec![MockResponse::json("[]")]).await;
+    let client = trading_client(&server);
+
+    let _ = client.execute(GetOpenPositions).await;
+    let _ = client
+        .execute(GetOpenPosition {
+            symbol_or_asset_id: SymbolOrAssetId::SymbolId("AAPL".into()),
+        })
+        .await;
+    let _ = client
+        .execute(ClosePosition {
+            symbol_or_asset_id: SymbolOrAssetId::SymbolId("AAPL".into()),
+            qty: None,
+            percentage: None,
+        })
+        .await;
+    let _ = client
+        .execute(CloseAllPositions {
+            cancel_orders: Some(true),
+        })
+        .await;
+    let _ = client
+        .execute(ExerciseOptionPosition {
+            symbol_or_contract_id: SymbolOrAssetId::SymbolId("AAPL240119C00150000".into()),
+        })
+        .await;
+
+    assert_eq!(
+        server.paths(),
+        [
+            request("GET", "/v2/positions"),
+            request("GET", "/v2/positions/AAPL"),
+            request("DELETE", "/v2/positions/AAPL"),
+            request("DELETE", "/v2/positions"),
+            request("POST", "/v2/positions/AAPL240119C00150000/exercise"),
+        ]
+    );
+}
+
+#[async_std::test]
+async fn account_and_clock_paths() {
+    let server = MockServer::start(vec![MockResponse::json("{}")]).await;
+    let client = trading_client(&server);
+
+    let _ = client.execute(GetAccount).await;
+    let _ = client.execute(GetClock).await;
+
+    assert_eq!(
+        server.paths(),
+        [request("GET", "/v2/account"), request("GET", "/v2/clock")]
+    );
+}
+
+#[async_std::test]
+async fn option_contract_paths() {
+    let server = MockServer::start(vec![MockResponse::json("{}")]).await;
+    let client = trading_client(&server);
+
+    let _ = client.execute(GetOptionContracts::default()).await;
+    let _ = client
+        .execute(GetOptionContract {
+            symbol_or_id: SymbolOrAssetId::SymbolId("AAPL240119C00150000".into()),
+        })
+        .await;
+
+    assert_eq!(
+        server.paths(),
+        [
+            request("GET", "/v2/options/contracts"),
+            request("GET", "/v2/options/contracts/AAPL240119C00150000"),
+        ]
+    );
+}
+
+#[async_std::test]
+async fn portfolio_history_path() {
+    let server = MockServer::start(vec![MockResponse::json("{}")]).await;
+    let client = trading_client(&server);
+
+    let _ = client.execute(GetPortfolioHistory::default()).await;
+
+    assert_eq!(
+        server.paths(),
+        [request("GET", "/v2/account/portfolio/history")]
+    );
+}
+
+#[async_std::test]
+async fn asset_paths() {
+    let server = MockServer::start(vec![MockResponse::json("[]")]).await;
+    let client = trading_client(&server);
+
+    let _ = client.execute(GetAssets::default()).await;
+    let _ = client
+        .execute(GetAsset {
+            symbol_or_asset_id: SymbolOrAssetId::SymbolId("AAPL".into()),
+        })
+        .await;
+
+    assert_eq!(
+        server.paths(),
+        [
+            request("GET", "/v2/assets"),
+            request("GET", "/v2/assets/AAPL"),
+        ]
+    );
+}
+
+#[async_std::test]
+async fn watchlist_paths() {
+    let server = MockServer::start(vec![MockResponse::json("[]")]).await;
+    let client = trading_client(&server);
+
+    let _ = client.execute(GetWatchlists).await;
+    let _ = client
+        .execute(GetWatchlist {
+            watchlist_id: "3174d6df".into(),
+        })
+        .await;
+    let _ = client
+        .execute(RemoveFromWatchlist {
+            watchlist_id: "3174d6df".into(),
+            symbol: "AAPL".into(),
+        })
+        .await;
+    let _ = client
+        .execute(DeleteWatchlist {
+            watchlist_id: "3174d6df".into(),
+        })
+        .await;
+
+    assert_eq!(
+        server.paths(),
+        [
+            request("GET", "/v2/watchlists"),
+            request("GET", "/v2/watchlists/3174d6df"),
+            request("DELETE", "/v2/watchlists/3174d6df/AAPL"),
+            request("DELETE", "/v2/watchlists/3174d6df"),
+        ]
+    );
+}
+
+#[async_std::test]
+async fn account_configuration_paths() {
+    let server = MockServer::start(vec![MockResponse::json("{}")]).await;
+    let client = trading_client(&server);
+
+    let _ = client.execute(GetAccountConfigurations).await;
+    let _ = client
+        .execute(UpdateAccountConfigurations {
+            suspend_trade: Some(true),
+            ..Default::default()
+        })
+        .await;
+
+    assert_eq!(
+        server.paths(),
+        [
+            request("GET", "/v2/account/configurations"),
+            request("PATCH", "/v2/account/configurations"),
+        ]
+    );
+    assert_eq!(
+        server.requests()[1].json(),
+        serde_json::json!({ "suspend_trade": true })
+    );
+}
+
+#[async_std::test]
+async fn activity_paths() {
+    let server = MockServer::start(vec![MockResponse::json("[]")]).await;
+    let client = trading_client(&server);
+
+    let _ = client.execute(GetAccountActivities::default()).await;
+    let _ = client
+        .execute(GetAccountActivitiesByType {
+            activity_type: ActivityType::Dividend,
+            date: None,
+            until: None,
+            after: None,
+            direction: None,
+            page_size: None,
+            page_token: None,
+        })
+        .await;
+
+    assert_eq!(
+        server.paths(),
+        [
+            request("GET", "/v2/account/activities"),
+            request("GET", "/v2/account/activities/DIV"),
+        ]
+    );
+}